@@ -0,0 +1,77 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use dd_algorithms_lib::{dd_bitset_len, get_k_dd_rand_num, get_k_dd_rand_num_large};
+
+fn groups_for(n: usize, k: usize) -> Vec<Vec<u128>> {
+    (0..n)
+        .map(|p| {
+            (0..k)
+                .map(|i| ((p as u128 + 1) * 0x9E37_79B9_7F4A_7C15) ^ ((i as u128) << 17))
+                .collect()
+        })
+        .collect()
+}
+
+/// 旧路径（每位参与者k个值，线性探测）与新路径（单个种子，Floyd抽样+位图）的对比
+fn bench_k_selection(c: &mut Criterion) {
+    let mut group = c.benchmark_group("k_selection");
+
+    // 旧路径中参与者数即候选数n；新路径让同样的n位参与者各提交一个值
+    for &(n, k) in &[(1_024usize, 1_000usize), (4_096, 1_000)] {
+        let owned = groups_for(n, k);
+        let groups: Vec<&[u128]> = owned.iter().map(|g| g.as_slice()).collect();
+        let values: Vec<u128> = owned.iter().map(|g| g[0]).collect();
+        let mut output = vec![0usize; k];
+        group.bench_with_input(
+            BenchmarkId::new("get_k_dd_rand_num", format!("n={n},k={k}")),
+            &n,
+            |b, &n| {
+                b.iter(|| {
+                    get_k_dd_rand_num(black_box(&groups), n, k, &mut output).unwrap();
+                    black_box(&output);
+                })
+            },
+        );
+
+        let mut bitset = vec![0u64; dd_bitset_len(n as u64)];
+        let mut output = vec![0u64; k];
+        group.bench_with_input(
+            BenchmarkId::new("get_k_dd_rand_num_large", format!("n={n},k={k}")),
+            &n,
+            |b, &n| {
+                b.iter(|| {
+                    get_k_dd_rand_num_large(
+                        black_box(&values),
+                        n,
+                        n as u64,
+                        &mut bitset,
+                        &mut output,
+                    )
+                    .unwrap();
+                    black_box(&output);
+                })
+            },
+        );
+    }
+
+    // 仅新路径可用的规模
+    let values = [1u128, 2, 3, 4];
+    for &(n, k) in &[(1u64 << 28, 100_000usize), (1_000_000, 1_000_000)] {
+        let mut bitset = vec![0u64; dd_bitset_len(n)];
+        let mut output = vec![0u64; k];
+        group.sample_size(10);
+        group.bench_with_input(
+            BenchmarkId::new("get_k_dd_rand_num_large", format!("n={n},k={k}")),
+            &n,
+            |b, &n| {
+                b.iter(|| {
+                    get_k_dd_rand_num_large(&values, 4, n, &mut bitset, &mut output).unwrap();
+                    black_box(&output);
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_k_selection);
+criterion_main!(benches);
@@ -1,5 +1,6 @@
 //! 公平分配和随机数生成的数学算法。
 
+use crate::rng::DdRng;
 use crate::{Error, Result};
 
 #[inline]
@@ -46,48 +47,8 @@ pub fn calculate_fair_division_equal_weights(values: &[i128], output: &mut [i128
         return Err(Error::NotEnoughParticipants);
     }
 
-    // 实现超级公平分配算法 - 等权重版本
-    let n = values.len() as i128;
-    let sum_v: i128 = values.iter().sum();
-
-    // 找到最高出价及其索引
-    let mut max_v = values[0];
-    let mut max_index = 0;
-    for (i, &v) in values.iter().enumerate() {
-        if v > max_v {
-            max_v = v;
-            max_index = i;
-        }
-    }
-
-    // 计算超级公平值增量 delta = (n*maxV-sumV) / (n*n)
-    let delta = match (n.checked_mul(max_v), n.checked_mul(n)) {
-        (Some(n_max_v), Some(n_squared)) => match n_max_v.checked_sub(sum_v) {
-            Some(numerator) => numerator / n_squared,
-            None => return Err(Error::CalculationFailed),
-        },
-        _ => return Err(Error::CalculationFailed),
-    };
-
-    // 计算每个参与者的分配
-    let mut sum_others = 0;
-
-    for (i, &v) in values.iter().enumerate() {
-        if i != max_index {
-            // 对于非最高出价者，分配为 v/n + delta
-            let share = v / n + delta;
-            output[i] = share;
-            sum_others += share;
-        } else {
-            // 为最高出价者保留空间，稍后填充
-            output[i] = 0;
-        }
-    }
-
-    // 将最高出价者的分配设置为其他参与者分配的负和
-    output[max_index] = -sum_others;
-
-    Ok(())
+    output.copy_from_slice(values);
+    super_fair_in_place(output, None)
 }
 
 /// 为权重不同的参与者计算超级公平分配。
@@ -141,21 +102,33 @@ pub fn calculate_fair_division_weighted(
         }
     }
 
-    // 实现超级公平分配算法 - 加权版本
-    let total_weight: i128 = weights.iter().sum();
-    let n = total_weight; // 总参与者数是权重之和
+    output.copy_from_slice(values);
+    super_fair_in_place(output, Some(weights))
+}
+
+/// 超级公平分配的计算部分：`buf`传入出价，原地写回分配结果。
+///
+/// 调用者负责验证参与者数量（>= 2）和权重（与`buf`等长且都为正数）；
+/// `weights`为None时所有权重都为1。
+pub(crate) fn super_fair_in_place(buf: &mut [i128], weights: Option<&[i128]>) -> Result<()> {
+    let weight = |i: usize| match weights {
+        Some(w) => w[i],
+        None => 1,
+    };
+
+    // 总参与者数是权重之和
+    let n: i128 = match weights {
+        Some(w) => w.iter().sum(),
+        None => buf.len() as i128,
+    };
 
     // 计算加权总值
-    let sum_v: i128 = values
-        .iter()
-        .zip(weights.iter())
-        .map(|(&v, &w)| v * w)
-        .sum();
+    let sum_v: i128 = buf.iter().enumerate().map(|(i, &v)| v * weight(i)).sum();
 
     // 找到最高出价及其索引
-    let mut max_v = values[0];
+    let mut max_v = buf[0];
     let mut max_index = 0;
-    for (i, &v) in values.iter().enumerate() {
+    for (i, &v) in buf.iter().enumerate() {
         if v > max_v {
             max_v = v;
             max_index = i;
@@ -171,23 +144,18 @@ pub fn calculate_fair_division_weighted(
         _ => return Err(Error::CalculationFailed),
     };
 
-    // 计算每个参与者的分配
+    // 计算每个参与者的分配：非最高出价者为 (v/n+delta)*weight
     let mut sum_others = 0;
-
-    for (i, (&v, &weight)) in values.iter().zip(weights.iter()).enumerate() {
+    for (i, slot) in buf.iter_mut().enumerate() {
         if i != max_index {
-            // 对于非最高出价者，分配为 (v/n+delta)*weight
-            let share = (v / n + delta) * weight;
-            output[i] = share;
+            let share = (*slot / n + delta) * weight(i);
+            *slot = share;
             sum_others += share;
-        } else {
-            // 为最高出价者保留空间，稍后填充
-            output[i] = 0;
         }
     }
 
     // 将最高出价者的分配设置为其他参与者分配的负和
-    output[max_index] = -sum_others;
+    buf[max_index] = -sum_others;
 
     Ok(())
 }
@@ -244,8 +212,8 @@ pub fn get_one_dd_rand_num(values: &[u128], n: usize, out: &mut u128) -> Result<
 
     // 使用异或运算对所有值进行计算
     let mut result = 0u128;
-    for i in 0..n {
-        result ^= values[i];
+    for &v in values {
+        result ^= v;
     }
 
     // 将结果存储在输出参数中
@@ -256,21 +224,24 @@ pub fn get_one_dd_rand_num(values: &[u128], n: usize, out: &mut u128) -> Result<
 /// 通过异或运算生成去中心化决策随机数（模仿福彩3D随机数算法）。
 ///
 /// 此函数实现去中心化随机数生成算法，用于模仿福彩3D的随机数生成机制。
-/// 多个参与者贡献值，结果是所有值的异或运算结果。使用异或运算可以保证概率相等，
+/// 多个参与者贡献值，结果是所有值的异或运算结果对k取模。使用异或运算可以保证概率相等，
 /// 只要保证k是2的n次幂即可。
 ///
+/// 需要真正的福彩3D号码（三位0–9数字）或其他彩票玩法时，请使用
+/// [`get_dd_lottery_draw`](crate::lottery::get_dd_lottery_draw)。
+///
 /// # 算法
 ///
 /// 1. 对输入数组中的所有值进行异或运算
 /// 2. 异或运算具有交换律和结合律，结果与顺序无关
-/// 3. 当k是2的n次幂时，异或运算能保证每个结果出现的概率相等
-/// 4. 返回u128类型的结果
+/// 3. 将结果对k取模；当k是2的n次幂时，取模只保留低位，每个结果出现的概率相等
+/// 4. 返回范围在[0, k)内的u128结果
 ///
 /// # 参数
 ///
 /// * `values` - 表示参与者输入数据的u128值切片
 /// * `n` - 输入数组中的值数量（应与values.len()匹配）
-/// * `k` - 用于验证的数值（必须大于0且必须是2的n次幂，通常为福彩3D的号码范围）
+/// * `k` - 号码范围（必须大于0且必须是2的n次幂）
 /// * `out` - 用于存储输出结果的可变引用
 ///
 /// # 返回值
@@ -284,10 +255,10 @@ pub fn get_one_dd_rand_num(values: &[u128], n: usize, out: &mut u128) -> Result<
 ///
 /// let values = [100u128, 200, 300, 400]; // 使用4个值
 /// let n = values.len();
-/// let k = 8; // 8是2的3次幂，福彩3D号码范围0-7
+/// let k = 8; // 8是2的3次幂，号码范围0-7
 /// let mut result = 0u128;
 /// get_one_dd_3d_rand_num(&values, n, k, &mut result).unwrap();
-/// // result = 100 ^ 200 ^ 300 ^ 400
+/// assert_eq!(result, (100 ^ 200 ^ 300 ^ 400) % 8);
 /// ```
 pub fn get_one_dd_3d_rand_num(values: &[u128], n: usize, k: usize, out: &mut u128) -> Result<()> {
     // 验证输入参数
@@ -307,12 +278,61 @@ pub fn get_one_dd_3d_rand_num(values: &[u128], n: usize, k: usize, out: &mut u12
 
     // 使用异或运算对所有值进行计算
     let mut result = 0u128;
-    for i in 0..n {
-        result ^= values[i];
+    for &v in values {
+        result ^= v;
+    }
+
+    // 取模得到[0, k)内的号码（k是2的n次幂，结果均匀分布）
+    *out = result % k as u128;
+    Ok(())
+}
+
+/// 使用去中心化随机数对切片进行确定性洗牌（完整随机排列）。
+///
+/// 先按`get_one_dd_rand_num`对所有参与者的值进行异或得到种子，再以种子创建`DdRng`
+/// （ChaCha20随机数流，与`select_k_eligible`等函数相同），驱动Fisher–Yates洗牌原地打乱`items`。
+/// 每一步都使用`DdRng::gen_below`的拒绝采样抽取下标，在种子均匀的前提下每个元素落在
+/// 每个位置的概率近似相等。注意：结果完全由128位种子决定，最多只能产生2^128种排列；
+/// 当长度 >= 35 时（35! > 2^128），大部分排列永远不会出现，因此不能视为在全部排列上均匀。
+/// 与`get_k_dd_rand_num`不同，此函数不使用栈上的固定数组，`items`的长度没有上限。
+///
+/// # 算法
+///
+/// 1. 计算种子：seed = 所有values的异或运算结果，并以seed创建`DdRng`
+/// 2. 对于i从len-1到1：从随机流中均匀抽取j ∈ [0, i]，交换items\[i\]和items\[j\]
+///
+/// # 参数
+///
+/// * `values` - 表示参与者输入数据的u128值切片
+/// * `n` - 输入数组中的值数量（应与values.len()匹配，且必须是2的n次幂）
+/// * `items` - 要原地打乱的切片（例如参与者列表、出场顺序）
+///
+/// # 返回值
+///
+/// * `Result<()>` - 成功时返回Ok，计算失败时返回Error
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::dd_shuffle;
+///
+/// let values = [100u128, 200, 300, 400];
+/// let mut order = [0usize, 1, 2, 3, 4, 5, 6, 7];
+/// dd_shuffle(&values, 4, &mut order).unwrap();
+/// let mut sorted = order;
+/// sorted.sort();
+/// assert_eq!(sorted, [0, 1, 2, 3, 4, 5, 6, 7]);
+/// ```
+pub fn dd_shuffle<T>(values: &[u128], n: usize, items: &mut [T]) -> Result<()> {
+    // 以异或种子创建随机数流（同时验证输入参数）
+    let mut rng = DdRng::from_contributions(values, n)?;
+
+    // Fisher–Yates洗牌
+    for i in (1..items.len()).rev() {
+        let j = rng.gen_below(i as u128 + 1) as usize;
+        items.swap(i, j);
     }
 
-    // 将结果存储在输出参数中
-    *out = result;
     Ok(())
 }
 
@@ -346,6 +366,8 @@ pub fn get_one_dd_3d_rand_num(values: &[u128], n: usize, k: usize, out: &mut u12
 /// * `k` 必须 <= 1,000（最大选择数）
 /// * `k` 必须 <= `n`（不能选择比可用参与者更多的参与者）
 ///
+/// 超出上述限制的大规模抽签请使用`get_k_dd_rand_num_large`。
+///
 /// # 返回值
 ///
 /// * `Result<()>` - 成功时返回Ok，计算失败时返回Error
@@ -462,8 +484,8 @@ pub fn get_k_dd_rand_num(
             // 通过检查现有输出条目找到第一个未使用的偏移量
             loop {
                 let mut found = false;
-                for j in 0..i {
-                    if output[j] == off_t {
+                for &prev in &output[..i] {
+                    if prev == off_t {
                         found = true;
                         break;
                     }
@@ -485,12 +507,134 @@ pub fn get_k_dd_rand_num(
     Ok(())
 }
 
+/// `get_k_dd_rand_num_large`所需的位图长度（u64字数）：ceil(n / 64)
+///
+/// 在32位目标（如wasm32）上字数超出usize范围时返回`usize::MAX`，分配必然失败而不会截断。
+pub fn dd_bitset_len(n: u64) -> usize {
+    usize::try_from(n / 64 + (n % 64 != 0) as u64).unwrap_or(usize::MAX)
+}
+
+/// 从大规模候选集合中不重复地选出k个索引（大规模抽签）。
+///
+/// `get_k_dd_rand_num`要求每位参与者提交k个值，并受栈上数组限制（n <= 100,000，
+/// k <= 1,000），冲突时线性探测的代价随选中比例增长。此函数只需每位参与者提交一个值：
+/// 以`get_one_dd_rand_num`的输出为种子创建`DdRng`随机数流，使用Floyd抽样算法
+/// 配合调用者提供的位图判重，再对结果做一次Fisher–Yates洗牌。
+/// 每一步只抽取一个随机数，期望时间复杂度为 O(k)，与n无关。
+///
+/// 在种子均匀的前提下，每个索引被选中的概率近似相等；结果由128位种子决定，
+/// 有序k元组的数目超过2^128时不能覆盖全部可能的结果。
+///
+/// # 算法
+///
+/// 1. 计算种子：seed = 所有values的异或运算结果，并以seed创建`DdRng`
+/// 2. 对于j从n-k到n-1：均匀抽取t ∈ [0, j]；若t已选中则选中j，否则选中t
+/// 3. 对选中的k个索引做Fisher–Yates洗牌，得到均匀随机的顺序
+/// 4. 清除位图中本次设置的位
+///
+/// # 参数
+///
+/// * `values` - 表示参与者输入数据的u128值切片
+/// * `m` - 参与者数量（应与values.len()匹配，且必须是2的n次幂）
+/// * `n` - 候选集合大小（任意正整数，不要求是2的n次幂）
+/// * `bitset` - 判重位图（长度必须 >= `dd_bitset_len(n)`，返回时恢复为全0）。
+///   调用者必须保证调用前全为0：为保持 O(k)，函数不扫描整个位图，只在抽样探测到的位置
+///   发现残留的位时返回InvalidInput；其他残留的位会使对应索引无法被选中
+/// * `output` - 用于存储选中索引的可变切片（长度k必须满足 0 < k <= n）
+///
+/// # 返回值
+///
+/// * `Result<()>` - 成功时返回Ok；输入无效、n超出本平台可寻址的位图范围或探测到残留的位时
+///   返回InvalidInput
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{dd_bitset_len, get_k_dd_rand_num_large};
+///
+/// let values = [100u128, 200, 300, 400];
+/// let n = 1_000_000u64;
+/// let mut bitset = vec![0u64; dd_bitset_len(n)];
+/// let mut output = [0u64; 5_000];
+/// get_k_dd_rand_num_large(&values, 4, n, &mut bitset, &mut output).unwrap();
+/// assert!(output.iter().all(|&x| x < n));
+/// // 位图已恢复为全0，可直接用于下一次抽签
+/// assert!(bitset.iter().all(|&w| w == 0));
+/// ```
+pub fn get_k_dd_rand_num_large(
+    values: &[u128],
+    m: usize,
+    n: u64,
+    bitset: &mut [u64],
+    output: &mut [u64],
+) -> Result<()> {
+    // 验证输入参数
+    let k = output.len() as u64;
+    if n == 0 || k == 0 || k > n {
+        return Err(Error::InvalidInput);
+    }
+    // 32位目标上字数可能超出usize，不能截断；此后位图下标的转换都不会截断
+    let words = usize::try_from(n / 64 + (n % 64 != 0) as u64).map_err(|_| Error::InvalidInput)?;
+    if bitset.len() < words {
+        return Err(Error::InvalidInput);
+    }
+    let mut rng = DdRng::from_contributions(values, m)?;
+
+    // Floyd抽样：每个j恰好抽取一次随机数
+    for (i, j) in (n - k..n).enumerate() {
+        let t = rng.gen_below(j as u128 + 1) as u64;
+        let pick = if bit_is_set(bitset, t) { j } else { t };
+        if bit_is_set(bitset, pick) {
+            // j不可能已被本次选中，说明调用前位图未清零
+            clear_selected(bitset, &output[..i]);
+            return Err(Error::InvalidInput);
+        }
+        set_bit(bitset, pick, true);
+        output[i] = pick;
+    }
+
+    // Floyd抽样得到的集合是均匀的，但顺序不是；洗牌得到均匀的顺序
+    for i in (1..output.len()).rev() {
+        let j = rng.gen_below(i as u128 + 1) as usize;
+        output.swap(i, j);
+    }
+
+    clear_selected(bitset, output);
+    Ok(())
+}
+
+// idx < n，调用者已验证 ceil(n / 64) 可以用usize表示，因此字下标的转换不会截断
+#[inline]
+fn bit_is_set(bitset: &[u64], idx: u64) -> bool {
+    bitset[(idx / 64) as usize] >> (idx % 64) & 1 == 1
+}
+
+#[inline]
+fn set_bit(bitset: &mut [u64], idx: u64, value: bool) {
+    let word = &mut bitset[(idx / 64) as usize];
+    if value {
+        *word |= 1 << (idx % 64);
+    } else {
+        *word &= !(1 << (idx % 64));
+    }
+}
+
+/// 清除本次选中的位，使位图恢复为调用前的状态
+fn clear_selected(bitset: &mut [u64], selected: &[u64]) {
+    for &idx in selected {
+        set_bit(bitset, idx, false);
+    }
+}
+
 /// 生成k个去中心化随机数，带有排除索引的白名单
 ///
 /// 此函数与`get_k_dd_rand_num`相同，但添加了一个`whitelist`，
 /// 包含永远不能被选择的索引。使用异或运算可以保证概率相等，
 /// 只要保证n是2的n次幂即可。所有其他行为和约束条件保持不变。
 ///
+/// 注意：`whitelist`是排除列表。如需只从给定集合中选择（包含语义），
+/// 请使用`select_k_eligible`和`Eligibility::Only`。
+///
 /// # 参数
 ///
 /// * `groups` - n组的切片，每组包含k个值
@@ -636,8 +780,8 @@ pub fn get_k_dd_rand_num_with_whitelist(
         loop {
             let mut found = false;
             // 检查之前的选择
-            for j in 0..i {
-                if output[j] == off_t {
+            for &prev in &output[..i] {
+                if prev == off_t {
                     found = true;
                     break;
                 }
@@ -0,0 +1,207 @@
+//! 席位分配（apportionment）方法。
+//!
+//! 按得票或质押比例在多个团体之间分配席位。所有比较都使用精确的整数
+//! 交叉相乘完成，平局由去中心化随机种子确定性地裁决。
+
+use core::cmp::Ordering;
+
+use crate::math::{cmp_mul3, mix_u128, mul_div_rem};
+use crate::{Error, Result};
+
+/// 最大参与分配的团体数
+pub const MAX_APPORTIONMENT_PARTIES: usize = 1_000;
+
+/// 最大席位数
+pub const MAX_APPORTIONMENT_SEATS: usize = 100_000;
+
+/// 席位分配方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApportionmentMethod {
+    /// D'Hondt最高平均数法，除数序列 1, 2, 3, ...
+    DHondt,
+    /// Sainte-Laguë法，除数序列 1, 3, 5, ...
+    SainteLague,
+    /// Huntington–Hill法，除数为 sqrt(s(s+1))，每个有效团体优先获得一席
+    HuntingtonHill,
+    /// Hamilton最大余额法（Hare份额）
+    Hamilton,
+}
+
+/// 按得票比例在各团体之间分配席位。
+///
+/// 得票为0或低于门槛的团体不参与分配。除数法每次把一个席位分给当前
+/// 商数最大的团体；最大余额法先按份额取整分配，再按余额从大到小分配剩余席位。
+///
+/// # 平局处理
+///
+/// 商数或余额完全相同时，由`seed`和团体索引派生的优先级决定，
+/// 通常将`get_one_dd_rand_num`的输出作为`seed`，保证平局裁决不可被单方操控。
+///
+/// # 参数
+///
+/// * `votes` - 每个团体的得票（或质押量）
+/// * `seats` - 待分配的席位数（必须 <= 100,000）
+/// * `method` - 席位分配方法
+/// * `threshold_bps` - 参与分配的最低得票比例，单位为万分之一（0表示无门槛，必须 <= 10,000）
+/// * `seed` - 用于平局裁决的去中心化随机数
+/// * `output` - 用于存储每个团体所得席位数的可变切片（应与votes.len()匹配）
+///
+/// # 返回值
+///
+/// * `Result<()>` - 成功时返回Ok，没有团体达到门槛时返回Error
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{calculate_apportionment, ApportionmentMethod};
+///
+/// let votes = [340_000u128, 280_000, 160_000, 60_000, 15_000];
+/// let mut seats = [0usize; 5];
+/// calculate_apportionment(&votes, 7, ApportionmentMethod::DHondt, 0, 0, &mut seats).unwrap();
+/// assert_eq!(seats, [3, 3, 1, 0, 0]);
+/// ```
+pub fn calculate_apportionment(
+    votes: &[u128],
+    seats: usize,
+    method: ApportionmentMethod,
+    threshold_bps: u32,
+    seed: u128,
+    output: &mut [usize],
+) -> Result<()> {
+    // 验证输入参数
+    if votes.is_empty() || votes.len() > MAX_APPORTIONMENT_PARTIES || output.len() != votes.len() {
+        return Err(Error::InvalidInput);
+    }
+    if seats == 0 || seats > MAX_APPORTIONMENT_SEATS || threshold_bps > 10_000 {
+        return Err(Error::InvalidInput);
+    }
+
+    let mut total = 0u128;
+    for &v in votes {
+        total = total.checked_add(v).ok_or(Error::CalculationFailed)?;
+    }
+    if total == 0 {
+        return Err(Error::InvalidInput);
+    }
+
+    // 标记达到门槛的团体：votes * 10000 >= total * threshold_bps
+    let mut eligible = [false; MAX_APPORTIONMENT_PARTIES];
+    let mut eligible_total = 0u128;
+    for (i, &v) in votes.iter().enumerate() {
+        let passes = cmp_mul3(v, 10_000, 1, total, threshold_bps as u128, 1) != Ordering::Less;
+        if v > 0 && passes {
+            eligible[i] = true;
+            eligible_total += v;
+        }
+        output[i] = 0;
+    }
+    if eligible_total == 0 {
+        return Err(Error::InvalidInput);
+    }
+
+    match method {
+        ApportionmentMethod::Hamilton => {
+            largest_remainder(votes, &eligible, eligible_total, seats, seed, output)
+        }
+        _ => highest_averages(votes, &eligible, seats, method, seed, output),
+    }
+}
+
+/// 比较两个团体在除数法下的商数 votes / divisor(seats)
+fn cmp_quotient(method: ApportionmentMethod, va: u128, sa: usize, vb: u128, sb: usize) -> Ordering {
+    let (sa, sb) = (sa as u64, sb as u64);
+    match method {
+        // va/(sa+1) 与 vb/(sb+1) 交叉相乘比较
+        ApportionmentMethod::DHondt => cmp_mul3(va, 1, sb + 1, vb, 1, sa + 1),
+        ApportionmentMethod::SainteLague => cmp_mul3(va, 1, 2 * sb + 1, vb, 1, 2 * sa + 1),
+        // 比较平方：va²·sb(sb+1) 与 vb²·sa(sa+1)；尚无席位的团体商数为无穷大
+        _ => match (sa, sb) {
+            (0, 0) => va.cmp(&vb),
+            (0, _) => Ordering::Greater,
+            (_, 0) => Ordering::Less,
+            _ => cmp_mul3(va, va, sb * (sb + 1), vb, vb, sa * (sa + 1)),
+        },
+    }
+}
+
+fn highest_averages(
+    votes: &[u128],
+    eligible: &[bool; MAX_APPORTIONMENT_PARTIES],
+    seats: usize,
+    method: ApportionmentMethod,
+    seed: u128,
+    output: &mut [usize],
+) -> Result<()> {
+    for _ in 0..seats {
+        let mut best: Option<usize> = None;
+        for i in 0..votes.len() {
+            if !eligible[i] {
+                continue;
+            }
+            let better = match best {
+                None => true,
+                Some(b) => match cmp_quotient(method, votes[i], output[i], votes[b], output[b]) {
+                    Ordering::Greater => true,
+                    Ordering::Less => false,
+                    Ordering::Equal => mix_u128(seed, i as u128) > mix_u128(seed, b as u128),
+                },
+            };
+            if better {
+                best = Some(i);
+            }
+        }
+        let b = best.ok_or(Error::CalculationFailed)?;
+        output[b] += 1;
+    }
+    Ok(())
+}
+
+fn largest_remainder(
+    votes: &[u128],
+    eligible: &[bool; MAX_APPORTIONMENT_PARTIES],
+    eligible_total: u128,
+    seats: usize,
+    seed: u128,
+    output: &mut [usize],
+) -> Result<()> {
+    // 按Hare份额取整分配，记录余额
+    let mut remainder = [0u128; MAX_APPORTIONMENT_PARTIES];
+    let mut allocated = 0usize;
+    for i in 0..votes.len() {
+        if !eligible[i] {
+            continue;
+        }
+        let (q, r) =
+            mul_div_rem(votes[i], seats as u128, eligible_total).ok_or(Error::CalculationFailed)?;
+        output[i] = q as usize;
+        remainder[i] = r;
+        allocated += q as usize;
+    }
+
+    // 剩余席位按余额从大到小分配，每个团体最多再得一席
+    let mut bonus = [false; MAX_APPORTIONMENT_PARTIES];
+    while allocated < seats {
+        let mut best: Option<usize> = None;
+        for i in 0..votes.len() {
+            if !eligible[i] || bonus[i] {
+                continue;
+            }
+            let better = match best {
+                None => true,
+                Some(b) => match remainder[i].cmp(&remainder[b]) {
+                    Ordering::Greater => true,
+                    Ordering::Less => false,
+                    Ordering::Equal => mix_u128(seed, i as u128) > mix_u128(seed, b as u128),
+                },
+            };
+            if better {
+                best = Some(i);
+            }
+        }
+        let b = best.ok_or(Error::CalculationFailed)?;
+        bonus[b] = true;
+        output[b] += 1;
+        allocated += 1;
+    }
+    Ok(())
+}
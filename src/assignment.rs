@@ -0,0 +1,902 @@
+//! 无货币的不可分物品分配（如资助名额、房间）。
+//!
+//! * `random_serial_dictatorship` - 随机序列独裁：由去中心化洗牌决定挑选顺序，
+//!   每位参与者依次拿走自己最喜欢的剩余物品
+//! * `probabilistic_serial` - 概率序列（"吃"）机制：所有参与者以相同速度同时"吃"
+//!   自己最喜欢的剩余物品，得到精确的分数分配矩阵
+//! * `birkhoff_von_neumann` - 将双随机整数矩阵分解为置换矩阵的凸组合
+//! * `sample_probabilistic_serial` - 以去中心化随机数从分数分配的BvN分解中抽取一个确定分配
+//!
+//! 分数分配以整数分子和公共分母表示，全部为精确整数运算。
+
+use crate::algorithms::{dd_shuffle, get_one_dd_rand_num};
+use crate::math::gcd;
+use crate::rng::DdRng;
+use crate::{Error, Result};
+
+/// 随机序列独裁支持的最大参与者数和物品数
+pub const MAX_ASSIGNMENT_SIZE: usize = 256;
+
+/// 概率序列机制支持的最大参与者数和物品数（保证公共分母不超过u128）
+pub const MAX_PS_SIZE: usize = 16;
+
+/// `birkhoff_von_neumann`支持的最大矩阵阶数
+pub const MAX_BVN_SIZE: usize = 2 * MAX_PS_SIZE;
+
+/// 验证偏好列表：物品索引 < m 且不重复
+fn validate_preferences(preferences: &[&[usize]], m: usize) -> Result<()> {
+    for prefs in preferences {
+        let mut seen = [false; MAX_ASSIGNMENT_SIZE];
+        for &item in prefs.iter() {
+            if item >= m || seen[item] {
+                return Err(Error::InvalidInput);
+            }
+            seen[item] = true;
+        }
+    }
+    Ok(())
+}
+
+/// 随机序列独裁（Random Serial Dictatorship）。
+///
+/// 挑选顺序由`dd_shuffle`对参与者索引洗牌得到；按此顺序，每位参与者拿走自己
+/// 偏好列表中排名最高的剩余物品，列表中的物品都已被拿走时不分配。
+///
+/// # 参数
+///
+/// * `values` - 表示参与者输入数据的u128值切片
+/// * `n` - 输入数组中的值数量（应与values.len()匹配，且必须是2的n次幂）
+/// * `preferences` - 每位参与者可接受的物品索引，按偏好从高到低排列（不可重复，参与者数 <= 256）
+/// * `m` - 物品数量（<= 256）
+/// * `order` - 用于存储挑选顺序的可变切片（应与preferences.len()匹配）
+/// * `assignment` - 用于存储每位参与者分得物品的可变切片（应与preferences.len()匹配）
+///
+/// # 返回值
+///
+/// * `Result<()>` - 成功时返回Ok，输入无效时返回InvalidInput
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::random_serial_dictatorship;
+///
+/// let values = [100u128, 200, 300, 400];
+/// let p0 = [0usize, 1, 2];
+/// let p1 = [0usize, 2];
+/// let p2 = [0usize];
+/// let preferences = [p0.as_slice(), p1.as_slice(), p2.as_slice()];
+/// let mut order = [0usize; 3];
+/// let mut assignment = [None; 3];
+/// random_serial_dictatorship(&values, 4, &preferences, 3, &mut order, &mut assignment).unwrap();
+/// // 第一个挑选的参与者得到物品0
+/// assert_eq!(assignment[order[0]], Some(0));
+/// ```
+pub fn random_serial_dictatorship(
+    values: &[u128],
+    n: usize,
+    preferences: &[&[usize]],
+    m: usize,
+    order: &mut [usize],
+    assignment: &mut [Option<usize>],
+) -> Result<()> {
+    // 验证输入参数
+    let agents = preferences.len();
+    if agents == 0
+        || agents > MAX_ASSIGNMENT_SIZE
+        || m > MAX_ASSIGNMENT_SIZE
+        || order.len() != agents
+        || assignment.len() != agents
+    {
+        return Err(Error::InvalidInput);
+    }
+    validate_preferences(preferences, m)?;
+
+    for (i, o) in order.iter_mut().enumerate() {
+        *o = i;
+    }
+    dd_shuffle(values, n, order)?;
+
+    let mut taken = [false; MAX_ASSIGNMENT_SIZE];
+    for &agent in order.iter() {
+        let pick = preferences[agent]
+            .iter()
+            .copied()
+            .find(|&item| !taken[item]);
+        if let Some(item) = pick {
+            taken[item] = true;
+        }
+        assignment[agent] = pick;
+    }
+    Ok(())
+}
+
+/// 概率序列机制（Bogomolnaia–Moulin "吃"机制）。
+///
+/// 每个物品的供给为1。时间从0到1，每位参与者以速度1"吃"自己偏好列表中排名最高的
+/// 未吃完物品，列表中的物品都吃完后停止。参与者i吃到物品j的份额即其获得j的概率。
+///
+/// 结果以公共分母表示：参与者i获得物品j的概率 = matrix\[i * m + j\] / 返回值。
+/// 每行之和 <= 分母（偏好列表足够长时恰好等于），每列之和 <= 分母。
+///
+/// # 参数
+///
+/// * `preferences` - 每位参与者可接受的物品索引，按偏好从高到低排列（不可重复，参与者数 <= 16）
+/// * `m` - 物品数量（1 <= m <= 16）
+/// * `matrix` - 用于存储分数分配分子的可变切片（长度为 参与者数 * m，按行排列）
+///
+/// # 返回值
+///
+/// * `Result<u128>` - 成功时返回公共分母
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::probabilistic_serial;
+///
+/// // 两人都最喜欢物品0
+/// let p = [0usize, 1];
+/// let preferences = [p.as_slice(), p.as_slice()];
+/// let mut matrix = [0u128; 4];
+/// let d = probabilistic_serial(&preferences, 2, &mut matrix).unwrap();
+/// assert_eq!(matrix.map(|x| x * 2 / d), [1, 1, 1, 1]); // 各得1/2
+/// ```
+pub fn probabilistic_serial(
+    preferences: &[&[usize]],
+    m: usize,
+    matrix: &mut [u128],
+) -> Result<u128> {
+    // 验证输入参数
+    let agents = preferences.len();
+    if agents == 0
+        || agents > MAX_PS_SIZE
+        || m == 0
+        || m > MAX_PS_SIZE
+        || matrix.len() != agents * m
+    {
+        return Err(Error::InvalidInput);
+    }
+    validate_preferences(preferences, m)?;
+
+    // 所有量都表示为 分子 / denominator
+    let mut denominator = 1u128;
+    let mut time = 0u128;
+    let mut remaining = [1u128; MAX_PS_SIZE];
+    matrix.iter_mut().for_each(|x| *x = 0);
+
+    while time < denominator {
+        // 每位参与者当前在吃的物品
+        let mut eating = [None; MAX_PS_SIZE];
+        let mut eaters = [0u128; MAX_PS_SIZE];
+        for (i, prefs) in preferences.iter().enumerate() {
+            eating[i] = prefs.iter().copied().find(|&j| remaining[j] > 0);
+            if let Some(j) = eating[i] {
+                eaters[j] += 1;
+            }
+        }
+        if eating[..agents].iter().all(Option::is_none) {
+            break;
+        }
+
+        // 本阶段时长：最先吃完的物品所需的时间，或剩余时间
+        let (mut num, mut den) = (denominator - time, 1u128);
+        for j in 0..m {
+            if eaters[j] > 0 && remaining[j] * den < num * eaters[j] {
+                num = remaining[j];
+                den = eaters[j];
+            }
+        }
+
+        // 扩大公共分母使时长为整数
+        let factor = den / gcd(num, den);
+        if factor > 1 {
+            denominator = denominator
+                .checked_mul(factor)
+                .ok_or(Error::CalculationFailed)?;
+            time *= factor;
+            remaining[..m].iter_mut().for_each(|r| *r *= factor);
+            matrix.iter_mut().for_each(|x| *x *= factor);
+            num *= factor;
+        }
+        let step = num / den;
+
+        time += step;
+        for (i, e) in eating[..agents].iter().enumerate() {
+            if let Some(j) = *e {
+                matrix[i * m + j] += step;
+                remaining[j] -= step;
+            }
+        }
+    }
+
+    // 约分
+    let g = matrix.iter().fold(denominator, |g, &x| gcd(g, x));
+    matrix.iter_mut().for_each(|x| *x /= g);
+    Ok(denominator / g)
+}
+
+/// 在正元素构成的二部图上寻找完美匹配（Kuhn算法），perm\[行\] = 列
+fn perfect_matching(matrix: &[u128], size: usize, perm: &mut [usize]) -> bool {
+    fn augment(
+        matrix: &[u128],
+        size: usize,
+        row: usize,
+        visited: &mut [bool],
+        col_owner: &mut [usize],
+    ) -> bool {
+        for col in 0..size {
+            if matrix[row * size + col] > 0 && !visited[col] {
+                visited[col] = true;
+                if col_owner[col] == usize::MAX
+                    || augment(matrix, size, col_owner[col], visited, col_owner)
+                {
+                    col_owner[col] = row;
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    let mut col_owner = [usize::MAX; MAX_BVN_SIZE];
+    for row in 0..size {
+        let mut visited = [false; MAX_BVN_SIZE];
+        if !augment(matrix, size, row, &mut visited, &mut col_owner[..size]) {
+            return false;
+        }
+    }
+    for (col, &row) in col_owner[..size].iter().enumerate() {
+        perm[row] = col;
+    }
+    true
+}
+
+/// 依次产生BvN分解的各项，`visit`返回false时提前停止
+fn decompose(
+    matrix: &[u128],
+    size: usize,
+    visit: &mut dyn FnMut(u128, &[usize]) -> bool,
+) -> Result<()> {
+    if size == 0 || size > MAX_BVN_SIZE || matrix.len() != size * size {
+        return Err(Error::InvalidInput);
+    }
+    // 每行、每列之和必须相等
+    let total: u128 = matrix[..size].iter().sum();
+    for i in 0..size {
+        let row: u128 = matrix[i * size..(i + 1) * size].iter().sum();
+        let col: u128 = (0..size).map(|r| matrix[r * size + i]).sum();
+        if row != total || col != total {
+            return Err(Error::InvalidInput);
+        }
+    }
+
+    let mut work = [0u128; MAX_BVN_SIZE * MAX_BVN_SIZE];
+    work[..size * size].copy_from_slice(matrix);
+    let mut left = total;
+    let mut perm = [0usize; MAX_BVN_SIZE];
+    while left > 0 {
+        if !perfect_matching(&work[..size * size], size, &mut perm) {
+            return Err(Error::CalculationFailed);
+        }
+        let weight = (0..size)
+            .map(|r| work[r * size + perm[r]])
+            .min()
+            .unwrap_or(0);
+        for r in 0..size {
+            work[r * size + perm[r]] -= weight;
+        }
+        left -= weight;
+        if !visit(weight, &perm[..size]) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Birkhoff–von Neumann分解：将行和、列和都相等的非负整数矩阵分解为置换矩阵的加权和。
+///
+/// 第t项的权重为weights\[t\]，置换为permutations\[t * size..(t + 1) * size\]
+/// （第r行对应第perm\[r\]列）。各项权重之和等于矩阵的行和，项数不超过 size² - 2·size + 2。
+///
+/// # 参数
+///
+/// * `matrix` - size × size 的非负整数矩阵（按行排列，size <= 32，各行、各列之和相等）
+/// * `size` - 矩阵阶数
+/// * `weights` - 用于存储各项权重的可变切片
+/// * `permutations` - 用于存储各项置换的可变切片（长度 >= weights.len() * size）
+///
+/// # 返回值
+///
+/// * `Result<usize>` - 成功时返回项数；矩阵无效或缓冲区不足时返回InvalidInput
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::birkhoff_von_neumann;
+///
+/// let matrix = [3u128, 1, 1, 3];
+/// let mut weights = [0u128; 2];
+/// let mut permutations = [0usize; 4];
+/// let terms = birkhoff_von_neumann(&matrix, 2, &mut weights, &mut permutations).unwrap();
+/// assert_eq!(terms, 2);
+/// assert_eq!(weights.iter().sum::<u128>(), 4);
+/// ```
+pub fn birkhoff_von_neumann(
+    matrix: &[u128],
+    size: usize,
+    weights: &mut [u128],
+    permutations: &mut [usize],
+) -> Result<usize> {
+    if permutations.len() < weights.len() * size {
+        return Err(Error::InvalidInput);
+    }
+    let mut terms = 0usize;
+    let mut overflow = false;
+    decompose(matrix, size, &mut |weight, perm| {
+        if terms == weights.len() {
+            overflow = true;
+            return false;
+        }
+        weights[terms] = weight;
+        permutations[terms * size..(terms + 1) * size].copy_from_slice(perm);
+        terms += 1;
+        true
+    })?;
+    if overflow {
+        return Err(Error::InvalidInput);
+    }
+    Ok(terms)
+}
+
+/// 以去中心化随机数从概率序列分配中抽取一个确定分配。
+///
+/// 先计算`probabilistic_serial`的分数分配P（参与者数a，物品数m），将其扩展为
+/// (a + m) 阶双随机矩阵 \[\[P, diag(1 - 行和)\], \[diag(1 - 列和), Pᵀ\]\]，
+/// 再做BvN分解，按权重抽取一项。参与者i得到物品j的概率恰好等于P\[i\]\[j\]。
+///
+/// # 参数
+///
+/// * `values` - 表示参与者输入数据的u128值切片
+/// * `n` - 输入数组中的值数量（应与values.len()匹配，且必须是2的n次幂）
+/// * `preferences` - 每位参与者可接受的物品索引，按偏好从高到低排列（参与者数 <= 16）
+/// * `m` - 物品数量（1 <= m <= 16）
+/// * `assignment` - 用于存储每位参与者分得物品的可变切片（应与preferences.len()匹配）
+///
+/// # 返回值
+///
+/// * `Result<()>` - 成功时返回Ok，输入无效时返回InvalidInput
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::sample_probabilistic_serial;
+///
+/// let values = [100u128, 200, 300, 400];
+/// let p = [0usize, 1];
+/// let preferences = [p.as_slice(), p.as_slice()];
+/// let mut assignment = [None; 2];
+/// sample_probabilistic_serial(&values, 4, &preferences, 2, &mut assignment).unwrap();
+/// // 两个物品各分给一人
+/// assert!(assignment == [Some(0), Some(1)] || assignment == [Some(1), Some(0)]);
+/// ```
+pub fn sample_probabilistic_serial(
+    values: &[u128],
+    n: usize,
+    preferences: &[&[usize]],
+    m: usize,
+    assignment: &mut [Option<usize>],
+) -> Result<()> {
+    let agents = preferences.len();
+    if assignment.len() != agents {
+        return Err(Error::InvalidInput);
+    }
+    let mut seed = 0u128;
+    get_one_dd_rand_num(values, n, &mut seed)?;
+
+    let mut fractional = [0u128; MAX_PS_SIZE * MAX_PS_SIZE];
+    let denominator = probabilistic_serial(preferences, m, &mut fractional[..agents * m])?;
+
+    // 扩展为双随机矩阵
+    let size = agents + m;
+    let mut extended = [0u128; MAX_BVN_SIZE * MAX_BVN_SIZE];
+    for i in 0..agents {
+        let row = &fractional[i * m..(i + 1) * m];
+        for (j, &p) in row.iter().enumerate() {
+            extended[i * size + j] = p;
+            extended[(agents + j) * size + m + i] = p;
+        }
+        extended[i * size + m + i] = denominator - row.iter().sum::<u128>();
+    }
+    for j in 0..m {
+        let col: u128 = (0..agents).map(|i| fractional[i * m + j]).sum();
+        extended[(agents + j) * size + j] = denominator - col;
+    }
+
+    // 按权重抽取一项
+    let target = DdRng::new(seed).gen_below(denominator);
+    let mut cumulative = 0u128;
+    let mut chosen = [0usize; MAX_BVN_SIZE];
+    decompose(&extended[..size * size], size, &mut |weight, perm| {
+        cumulative += weight;
+        if target < cumulative {
+            chosen[..size].copy_from_slice(perm);
+            return false;
+        }
+        true
+    })?;
+
+    for (i, a) in assignment.iter_mut().enumerate() {
+        *a = if chosen[i] < m { Some(chosen[i]) } else { None };
+    }
+    Ok(())
+}
+
+/// 可加估值分配支持的最大参与者数
+pub const MAX_ALLOCATION_AGENTS: usize = 32;
+
+/// 验证可加估值矩阵与分配结果的形状，返回 (参与者数, 物品数)
+pub(crate) fn validate_valuations(
+    valuations: &[&[u128]],
+    allocation: &[usize],
+) -> Result<(usize, usize)> {
+    let n = valuations.len();
+    let m = allocation.len();
+    if n == 0 || n > MAX_ALLOCATION_AGENTS || m > MAX_ASSIGNMENT_SIZE {
+        return Err(Error::InvalidInput);
+    }
+    if valuations.iter().any(|row| row.len() != m) {
+        return Err(Error::InvalidInput);
+    }
+    Ok((n, m))
+}
+
+/// 解析挑选顺序：None为0..n，否则必须是0..n的一个排列
+pub(crate) fn resolve_order(order: Option<&[usize]>, n: usize, buf: &mut [usize]) -> Result<()> {
+    match order {
+        None => {
+            for (i, b) in buf[..n].iter_mut().enumerate() {
+                *b = i;
+            }
+        }
+        Some(order) => {
+            if order.len() != n {
+                return Err(Error::InvalidInput);
+            }
+            let mut seen = [false; MAX_ALLOCATION_AGENTS];
+            for &a in order {
+                if a >= n || seen[a] {
+                    return Err(Error::InvalidInput);
+                }
+                seen[a] = true;
+            }
+            buf[..n].copy_from_slice(order);
+        }
+    }
+    Ok(())
+}
+
+/// 轮流挑选（round-robin）分配不可分物品，结果满足EF1。
+///
+/// 参与者按挑选顺序轮流拿走自己估值最高的剩余物品（估值相同时取索引小者），
+/// 直到物品分完。挑选顺序可以由`get_k_dd_rand_num`（k = n）的输出随机化。
+///
+/// # 参数
+///
+/// * `valuations` - 每位参与者对每个物品的可加估值（参与者数 <= 32，每行长度为物品数m）
+/// * `order` - 挑选顺序（0..n的排列），None表示按索引顺序
+/// * `allocation` - 用于存储每个物品归属参与者的可变切片（长度为m，m <= 256）
+///
+/// # 返回值
+///
+/// * `Result<()>` - 成功时返回Ok，输入无效时返回InvalidInput
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{get_k_dd_rand_num, is_ef1, round_robin_allocation};
+///
+/// let v0 = [10u128, 8, 1, 1];
+/// let v1 = [10u128, 2, 6, 3];
+/// let valuations = [v0.as_slice(), v1.as_slice()];
+///
+/// // 用去中心化随机数决定挑选顺序
+/// let g0 = [111u128, 222];
+/// let g1 = [333u128, 444];
+/// let mut order = [0usize; 2];
+/// get_k_dd_rand_num(&[g0.as_slice(), g1.as_slice()], 2, 2, &mut order).unwrap();
+///
+/// let mut allocation = [0usize; 4];
+/// round_robin_allocation(&valuations, Some(&order), &mut allocation).unwrap();
+/// assert!(is_ef1(&valuations, &allocation).unwrap());
+/// ```
+pub fn round_robin_allocation(
+    valuations: &[&[u128]],
+    order: Option<&[usize]>,
+    allocation: &mut [usize],
+) -> Result<()> {
+    let (n, m) = validate_valuations(valuations, allocation)?;
+    let mut sequence = [0usize; MAX_ALLOCATION_AGENTS];
+    resolve_order(order, n, &mut sequence)?;
+
+    let mut taken = [false; MAX_ASSIGNMENT_SIZE];
+    for turn in 0..m {
+        let agent = sequence[turn % n];
+        let row = valuations[agent];
+        let mut best = usize::MAX;
+        for item in 0..m {
+            if !taken[item] && (best == usize::MAX || row[item] > row[best]) {
+                best = item;
+            }
+        }
+        taken[best] = true;
+        allocation[best] = agent;
+    }
+    Ok(())
+}
+
+/// 包络消除（envy-cycle elimination）分配不可分物品，结果满足EF1。
+///
+/// 按索引顺序逐个分配物品：若所有参与者都被嫉妒，先沿嫉妒环交换物品包（环上每位参与者
+/// 换成自己嫉妒的参与者的物品包），直到出现不被嫉妒的参与者，再把物品交给它
+/// （有多位时按挑选顺序取第一位）。
+/// 与轮流挑选相比，它不依赖参与者之间的挑选轮次，同样适用于可加估值。
+///
+/// # 参数
+///
+/// * `valuations` - 每位参与者对每个物品的可加估值（参与者数 <= 32，每行长度为物品数m）
+/// * `order` - 不被嫉妒者之间的优先顺序（0..n的排列），None表示按索引顺序
+/// * `allocation` - 用于存储每个物品归属参与者的可变切片（长度为m，m <= 256）
+///
+/// # 返回值
+///
+/// * `Result<()>` - 成功时返回Ok；输入无效时返回InvalidInput，估值之和溢出时返回CalculationFailed
+pub fn envy_cycle_elimination(
+    valuations: &[&[u128]],
+    order: Option<&[usize]>,
+    allocation: &mut [usize],
+) -> Result<()> {
+    let (n, m) = validate_valuations(valuations, allocation)?;
+    let mut sequence = [0usize; MAX_ALLOCATION_AGENTS];
+    resolve_order(order, n, &mut sequence)?;
+
+    // bundle_value[i * n + b] = 参与者i对物品包b的估值；holder[a] = 参与者a持有的物品包
+    let mut bundle_value = [0u128; MAX_ALLOCATION_AGENTS * MAX_ALLOCATION_AGENTS];
+    let mut holder = [0usize; MAX_ALLOCATION_AGENTS];
+    for (a, h) in holder[..n].iter_mut().enumerate() {
+        *h = a;
+    }
+    let envies = |bundle_value: &[u128], holder: &[usize], i: usize, j: usize| {
+        bundle_value[i * n + holder[j]] > bundle_value[i * n + holder[i]]
+    };
+
+    for item in 0..m {
+        // 消除嫉妒环，直到存在不被嫉妒的参与者
+        let recipient = loop {
+            let source = sequence[..n]
+                .iter()
+                .copied()
+                .find(|&j| (0..n).all(|i| !envies(&bundle_value, &holder, i, j)));
+            if let Some(agent) = source {
+                break agent;
+            }
+
+            // 每位参与者都被嫉妒：沿"被谁嫉妒"反向行走必然回到走过的参与者
+            let mut step = [usize::MAX; MAX_ALLOCATION_AGENTS];
+            let mut visited = [false; MAX_ALLOCATION_AGENTS];
+            let mut current = 0usize;
+            while !visited[current] {
+                visited[current] = true;
+                let envier = (0..n)
+                    .find(|&i| envies(&bundle_value, &holder, i, current))
+                    .ok_or(Error::CalculationFailed)?;
+                step[envier] = current;
+                current = envier;
+            }
+            // 环上的每位参与者换成自己嫉妒的参与者的物品包
+            let start = current;
+            let mut rotated = [0usize; MAX_ALLOCATION_AGENTS];
+            let mut a = start;
+            loop {
+                rotated[a] = holder[step[a]];
+                a = step[a];
+                if a == start {
+                    break;
+                }
+            }
+            let mut a = start;
+            loop {
+                holder[a] = rotated[a];
+                a = step[a];
+                if a == start {
+                    break;
+                }
+            }
+        };
+
+        let bundle = holder[recipient];
+        allocation[item] = bundle;
+        for i in 0..n {
+            let v = &mut bundle_value[i * n + bundle];
+            *v = v
+                .checked_add(valuations[i][item])
+                .ok_or(Error::CalculationFailed)?;
+        }
+    }
+
+    // 物品记录的是物品包编号，换算为最终持有者
+    let mut owner = [0usize; MAX_ALLOCATION_AGENTS];
+    for (a, &b) in holder[..n].iter().enumerate() {
+        owner[b] = a;
+    }
+    for a in allocation.iter_mut() {
+        *a = owner[*a];
+    }
+    Ok(())
+}
+
+/// 逐对检查无嫉妒性的松弛条件：v_i(B_i) >= v_i(B_j) - 可去掉的物品估值
+fn check_envy(
+    valuations: &[&[u128]],
+    allocation: &[usize],
+    removable: fn(max: u128, min_positive: u128) -> u128,
+) -> Result<bool> {
+    let (n, _) = validate_valuations(valuations, allocation)?;
+    if allocation.iter().any(|&a| a >= n) {
+        return Err(Error::InvalidInput);
+    }
+    for (i, row) in valuations.iter().enumerate() {
+        let mut bundles = [0u128; MAX_ALLOCATION_AGENTS];
+        let mut max = [0u128; MAX_ALLOCATION_AGENTS];
+        let mut min_positive = [u128::MAX; MAX_ALLOCATION_AGENTS];
+        for (&v, &a) in row.iter().zip(allocation) {
+            bundles[a] = bundles[a].checked_add(v).ok_or(Error::CalculationFailed)?;
+            max[a] = max[a].max(v);
+            if v > 0 {
+                min_positive[a] = min_positive[a].min(v);
+            }
+        }
+        for j in 0..n {
+            if j != i
+                && bundles[i] < bundles[j] - removable(max[j], min_positive[j]).min(bundles[j])
+            {
+                return Ok(false);
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// 检查分配是否满足EF1（envy-free up to one item）。
+///
+/// 对任意参与者i、j，若i嫉妒j，则去掉j的物品包中i估值最高的一个物品后i不再嫉妒。
+///
+/// # 参数
+///
+/// * `valuations` - 每位参与者对每个物品的可加估值（参与者数 <= 32）
+/// * `allocation` - 每个物品归属的参与者
+///
+/// # 返回值
+///
+/// * `Result<bool>` - 满足EF1时返回true
+pub fn is_ef1(valuations: &[&[u128]], allocation: &[usize]) -> Result<bool> {
+    check_envy(valuations, allocation, |max, _| max)
+}
+
+/// 检查分配是否满足EFX（envy-free up to any item）。
+///
+/// 对任意参与者i、j，去掉j的物品包中任意一个i估值为正的物品后i都不嫉妒j
+/// （即去掉i估值最低的正估值物品后仍不嫉妒）。EFX强于EF1，轮流挑选和包络消除
+/// 都只保证EF1。
+///
+/// # 参数
+///
+/// * `valuations` - 每位参与者对每个物品的可加估值（参与者数 <= 32）
+/// * `allocation` - 每个物品归属的参与者
+///
+/// # 返回值
+///
+/// * `Result<bool>` - 满足EFX时返回true
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{is_ef1, is_efx};
+///
+/// let v = [5u128, 3, 3];
+/// let valuations = [v.as_slice(), v.as_slice()];
+/// // 参与者1得到{1, 2}：参与者0去掉物品1后估值3 < 5，不嫉妒
+/// assert!(is_efx(&valuations, &[0, 1, 1]).unwrap());
+/// // 参与者0得到全部：去掉一个物品后参与者1仍嫉妒
+/// assert!(!is_ef1(&valuations, &[0, 0, 0]).unwrap());
+/// ```
+pub fn is_efx(valuations: &[&[u128]], allocation: &[usize]) -> Result<bool> {
+    check_envy(valuations, allocation, |_, min_positive| {
+        if min_positive == u128::MAX {
+            0
+        } else {
+            min_positive
+        }
+    })
+}
+
+/// 最大纳什福利求解器支持的最大参与者数
+pub const MAX_MNW_AGENTS: usize = 8;
+
+/// 最大纳什福利求解器支持的最大物品数
+pub const MAX_MNW_ITEMS: usize = 16;
+
+/// 纳什福利乘积的64位分段数（8个u128之积不超过1024位）
+pub const NASH_WELFARE_LIMBS: usize = 2 * MAX_MNW_AGENTS;
+
+/// 纳什福利（精确整数）
+///
+/// 按字典序比较：先比较效用为正的参与者数，再比较这些参与者效用的乘积。
+/// 所有参与者都能得到正效用时即为通常的纳什福利 Π uᵢ。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NashWelfare {
+    /// 效用为正的参与者数
+    pub positive_agents: usize,
+    /// 正效用之积（64位分段，高位在前）
+    pub product: [u64; NASH_WELFARE_LIMBS],
+}
+
+impl NashWelfare {
+    fn one() -> Self {
+        let mut product = [0u64; NASH_WELFARE_LIMBS];
+        product[NASH_WELFARE_LIMBS - 1] = 1;
+        NashWelfare {
+            positive_agents: 0,
+            product,
+        }
+    }
+
+    /// 乘以一个正效用
+    fn include(&mut self, utility: u128) {
+        let factor = [utility as u64, (utility >> 64) as u64];
+        let mut result = [0u64; NASH_WELFARE_LIMBS];
+        // 低位在分段数组末尾
+        for (fi, &f) in factor.iter().enumerate() {
+            let mut carry = 0u128;
+            for k in 0..NASH_WELFARE_LIMBS - fi {
+                let src = NASH_WELFARE_LIMBS - 1 - k;
+                let dst = src - fi;
+                let acc = self.product[src] as u128 * f as u128 + result[dst] as u128 + carry;
+                result[dst] = acc as u64;
+                carry = acc >> 64;
+            }
+        }
+        self.product = result;
+        self.positive_agents += 1;
+    }
+
+    /// 乘积能放进u128时返回乘积
+    pub fn product_u128(&self) -> Option<u128> {
+        let (high, low) = self.product.split_at(NASH_WELFARE_LIMBS - 2);
+        if high.iter().any(|&x| x != 0) {
+            return None;
+        }
+        Some(((low[0] as u128) << 64) | low[1] as u128)
+    }
+}
+
+/// 由效用计算纳什福利
+fn nash_welfare_of(utilities: &[u128]) -> NashWelfare {
+    let mut welfare = NashWelfare::one();
+    for &u in utilities.iter().filter(|&&u| u > 0) {
+        welfare.include(u);
+    }
+    welfare
+}
+
+/// 分支定界的搜索状态
+struct MnwSearch<'a> {
+    valuations: &'a [&'a [u128]],
+    n: usize,
+    m: usize,
+    /// suffix[i][t] = 参与者i对物品t..m的估值之和
+    suffix: [[u128; MAX_MNW_ITEMS + 1]; MAX_MNW_AGENTS],
+    current: [usize; MAX_MNW_ITEMS],
+    utilities: [u128; MAX_MNW_AGENTS],
+    best: Option<NashWelfare>,
+    best_allocation: [usize; MAX_MNW_ITEMS],
+}
+
+impl MnwSearch<'_> {
+    fn search(&mut self, item: usize) {
+        // 上界：每位参与者得到全部剩余物品
+        let mut bound = [0u128; MAX_MNW_AGENTS];
+        for (i, b) in bound[..self.n].iter_mut().enumerate() {
+            *b = self.utilities[i] + self.suffix[i][item];
+        }
+        if let Some(best) = self.best {
+            if nash_welfare_of(&bound[..self.n]) <= best {
+                return;
+            }
+        }
+        if item == self.m {
+            // 剩余物品为空时上界即为当前福利，且严格优于已有最优解
+            self.best = Some(nash_welfare_of(&self.utilities[..self.n]));
+            self.best_allocation = self.current;
+            return;
+        }
+        // 按参与者索引顺序展开，先找到的最优解即为字典序最小的分配
+        for agent in 0..self.n {
+            let v = self.valuations[agent][item];
+            self.current[item] = agent;
+            self.utilities[agent] += v;
+            self.search(item + 1);
+            self.utilities[agent] -= v;
+        }
+    }
+}
+
+/// 求纳什福利最大的不可分物品分配（精确分支定界，适用于小规模实例）。
+///
+/// 纳什福利最大的分配同时满足EF1和帕累托最优。不是所有参与者都能得到正效用时，
+/// 先最大化得到正效用的人数，再最大化这些人效用之积。最优分配不唯一时，
+/// 返回`allocation`按字典序最小的一个（物品0的归属最小，其次物品1，依此类推）。
+///
+/// 最坏情况下需要检查 n^m 个分配，请保持实例规模较小。
+///
+/// # 参数
+///
+/// * `valuations` - 每位参与者对每个物品的可加估值（参与者数 <= 8，每行长度为物品数m）
+/// * `allocation` - 用于存储每个物品归属参与者的可变切片（长度为m，m <= 16）
+/// * `utilities` - 用于存储每位参与者效用的可变切片（应与valuations.len()匹配）
+///
+/// # 返回值
+///
+/// * `Result<NashWelfare>` - 成功时返回最大纳什福利；输入无效时返回InvalidInput，
+///   估值之和溢出时返回CalculationFailed
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::maximum_nash_welfare;
+///
+/// let v0 = [6u128, 3, 1];
+/// let v1 = [4u128, 4, 2];
+/// let mut allocation = [0usize; 3];
+/// let mut utilities = [0u128; 2];
+/// let welfare =
+///     maximum_nash_welfare(&[v0.as_slice(), v1.as_slice()], &mut allocation, &mut utilities)
+///         .unwrap();
+/// assert_eq!(allocation, [0, 1, 1]);
+/// assert_eq!(utilities, [6, 6]);
+/// assert_eq!(welfare.product_u128(), Some(36));
+/// ```
+pub fn maximum_nash_welfare(
+    valuations: &[&[u128]],
+    allocation: &mut [usize],
+    utilities: &mut [u128],
+) -> Result<NashWelfare> {
+    let (n, m) = validate_valuations(valuations, allocation)?;
+    if n > MAX_MNW_AGENTS || m > MAX_MNW_ITEMS || utilities.len() != n {
+        return Err(Error::InvalidInput);
+    }
+
+    let mut search = MnwSearch {
+        valuations,
+        n,
+        m,
+        suffix: [[0; MAX_MNW_ITEMS + 1]; MAX_MNW_AGENTS],
+        current: [0; MAX_MNW_ITEMS],
+        utilities: [0; MAX_MNW_AGENTS],
+        best: None,
+        best_allocation: [0; MAX_MNW_ITEMS],
+    };
+    for (i, row) in valuations.iter().enumerate() {
+        for t in (0..m).rev() {
+            search.suffix[i][t] = search.suffix[i][t + 1]
+                .checked_add(row[t])
+                .ok_or(Error::CalculationFailed)?;
+        }
+    }
+    search.search(0);
+
+    allocation.copy_from_slice(&search.best_allocation[..m]);
+    utilities.iter_mut().for_each(|u| *u = 0);
+    for (item, &agent) in allocation.iter().enumerate() {
+        utilities[agent] += valuations[agent][item];
+    }
+    search.best.ok_or(Error::CalculationFailed)
+}
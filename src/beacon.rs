@@ -0,0 +1,146 @@
+//! 跨轮次链式随机信标。
+//!
+//! 周期性运行的随机信标中，每一轮的输出都必须依赖上一轮的输出，
+//! 使参与者无法脱离链条预先计算（grinding）有利的贡献值。本模块维护
+//! `(round, Timestamp, output)`状态，将上一轮输出与本轮贡献值通过选定的
+//! 组合器混合，并提供从创世种子重放整条链的验证函数。
+
+use crate::algorithms::get_one_dd_rand_num;
+use crate::rng::DdRng;
+use crate::types::Timestamp;
+use crate::{Error, Result};
+
+/// 上一轮输出与本轮贡献值的组合方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BeaconCombiner {
+    /// 输出 = 上一轮输出 ⊕ 本轮贡献值的异或结果（与`get_one_dd_rand_num`一致）
+    Xor,
+    /// 输出 = ChaCha20(密钥 = 本轮贡献值的异或结果 ‖ 上一轮输出) 的前128位（见`DdRng::chained`）
+    ChaCha20,
+}
+
+/// 信标状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BeaconState {
+    /// 轮次（创世状态为0）
+    pub round: u64,
+    /// 本轮时间戳
+    pub timestamp: Timestamp,
+    /// 本轮输出
+    pub output: u128,
+}
+
+impl BeaconState {
+    /// 创世状态：轮次0，输出为创世种子
+    pub fn genesis(seed: u128, timestamp: Timestamp) -> Self {
+        BeaconState {
+            round: 0,
+            timestamp,
+            output: seed,
+        }
+    }
+}
+
+/// 已发布的一轮信标记录
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BeaconRound<'a> {
+    /// 本轮时间戳
+    pub timestamp: Timestamp,
+    /// 本轮参与者的贡献值（数量必须是2的n次幂）
+    pub values: &'a [u128],
+    /// 发布的本轮输出
+    pub output: u128,
+}
+
+/// 按组合器混合上一轮输出与本轮贡献值的异或结果
+pub(crate) fn combine_beacon(previous: u128, contribution: u128, combiner: BeaconCombiner) -> u128 {
+    match combiner {
+        BeaconCombiner::Xor => previous ^ contribution,
+        BeaconCombiner::ChaCha20 => DdRng::chained(contribution, previous).next_u128(),
+    }
+}
+
+/// 计算信标的下一轮状态。
+///
+/// # 参数
+///
+/// * `state` - 上一轮状态
+/// * `timestamp` - 本轮时间戳（必须严格大于上一轮时间戳）
+/// * `values` - 本轮参与者的u128贡献值切片
+/// * `n` - 参与者数量（应与values.len()匹配，且必须是2的n次幂）
+/// * `combiner` - 组合方式
+///
+/// # 返回值
+///
+/// * `Result<BeaconState>` - 成功时返回本轮状态，时间戳未递增或轮次溢出时返回InvalidInput
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{next_beacon_round, BeaconCombiner, BeaconState};
+///
+/// let genesis = BeaconState::genesis(0x5eed, 1_700_000_000);
+/// let r1 = next_beacon_round(&genesis, 1_700_000_030, &[1, 2, 3, 4], 4, BeaconCombiner::ChaCha20)
+///     .unwrap();
+/// assert_eq!(r1.round, 1);
+/// let r2 = next_beacon_round(&r1, 1_700_000_060, &[1, 2, 3, 4], 4, BeaconCombiner::ChaCha20)
+///     .unwrap();
+/// // 相同的贡献值在不同轮次得到不同的输出
+/// assert_ne!(r1.output, r2.output);
+/// ```
+pub fn next_beacon_round(
+    state: &BeaconState,
+    timestamp: Timestamp,
+    values: &[u128],
+    n: usize,
+    combiner: BeaconCombiner,
+) -> Result<BeaconState> {
+    if timestamp <= state.timestamp {
+        return Err(Error::InvalidInput);
+    }
+    let round = state.round.checked_add(1).ok_or(Error::InvalidInput)?;
+
+    let mut contribution = 0u128;
+    get_one_dd_rand_num(values, n, &mut contribution)?;
+    let output = combine_beacon(state.output, contribution, combiner);
+
+    Ok(BeaconState {
+        round,
+        timestamp,
+        output,
+    })
+}
+
+/// 从创世状态重放整条信标链，验证每一轮发布的输出。
+///
+/// # 参数
+///
+/// * `genesis` - 创世状态
+/// * `rounds` - 按顺序排列的已发布轮次记录（第i项为第i+1轮）
+/// * `combiner` - 组合方式
+///
+/// # 返回值
+///
+/// * `Result<BeaconState>` - 所有轮次都验证通过时返回最后一轮的状态；
+///   某轮输出不一致时返回CalculationFailed，记录本身无效（时间戳未递增、
+///   贡献值数量不是2的n次幂等）时返回InvalidInput
+pub fn verify_beacon_chain(
+    genesis: &BeaconState,
+    rounds: &[BeaconRound],
+    combiner: BeaconCombiner,
+) -> Result<BeaconState> {
+    let mut state = *genesis;
+    for record in rounds {
+        state = next_beacon_round(
+            &state,
+            record.timestamp,
+            record.values,
+            record.values.len(),
+            combiner,
+        )?;
+        if state.output != record.output {
+            return Err(Error::CalculationFailed);
+        }
+    }
+    Ok(state)
+}
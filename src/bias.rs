@@ -0,0 +1,300 @@
+//! 合谋偏差分析。
+//!
+//! 量化一个合谋联盟能在多大程度上影响`get_k_dd_rand_num`的选择概率，
+//! 为审计时的参数选择提供依据。分析针对"使某个目标参与者被选中"这一目标，
+//! 比较诚实情况下的选中概率与联盟采用最优策略时可达到的选中概率。
+//!
+//! * `analyze_coalition_bias` - 解析计算（定点数，精确到`BIAS_SCALE`）
+//! * `simulate_coalition_bias` - 以真实的`get_k_dd_rand_num`进行蒙特卡洛模拟
+
+use crate::algorithms::get_k_dd_rand_num;
+use crate::math::{mix_u128, mul_div, WAD};
+use crate::{Error, Result};
+
+/// 概率定点数精度：1.0 = `BIAS_SCALE`
+pub const BIAS_SCALE: u128 = 1_000_000_000;
+
+/// 模拟支持的最大参与者数
+pub const MAX_BIAS_SIM_PARTICIPANTS: usize = 64;
+
+/// 模拟支持的最大选择数
+pub const MAX_BIAS_SIM_K: usize = 16;
+
+/// 模拟`SelectiveReveal`策略时支持的最大联盟人数（需要枚举2^c种揭示组合）
+pub const MAX_BIAS_SIM_SELECTIVE: usize = 10;
+
+/// 联盟策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoalitionStrategy {
+    /// 没有承诺约束：最后提交的成员看到其他人的值后选择自己的值。
+    /// 异或组合下一个成员即可令 自己的值 = 目标 ⊕ 其他值的异或，完全控制结果
+    ChooseAfterReveal,
+    /// 承诺后选择性揭示：每个成员可以揭示或扣留，扣留者按门限模式的`Exclude`策略
+    /// 不计入随机性。联盟可以在2^c种揭示组合中选择最有利的一种
+    SelectiveReveal,
+    /// 承诺后中止：任何成员扣留都会使本轮作废并重新抽取，每个成员最多中止一次，
+    /// 联盟最多得到c+1次独立抽取机会
+    AbortAndRetry,
+}
+
+/// 偏差分析报告（概率均为定点数，精度为`BIAS_SCALE`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BiasReport {
+    /// 诚实情况下目标参与者被选中的概率
+    pub honest_probability: u128,
+    /// 联盟采用最优策略时目标参与者被选中的概率
+    pub achievable_probability: u128,
+    /// 偏差：achievable_probability - honest_probability
+    pub bias: u128,
+    /// 联盟可以从中选择的独立结果数（超过u128时取u128::MAX）
+    pub outcomes: u128,
+}
+
+fn validate(n: usize, k: usize, coalition: usize) -> Result<()> {
+    if n == 0 || k == 0 || k > n || n & (n - 1) != 0 || coalition > n {
+        return Err(Error::InvalidInput);
+    }
+    Ok(())
+}
+
+/// 计算 base^exp（WAD定点数）
+fn pow_wad(base: u128, mut exp: u128) -> Result<u128> {
+    let mut result = WAD;
+    let mut b = base;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_div(result, b, WAD).ok_or(Error::CalculationFailed)?;
+        }
+        b = mul_div(b, b, WAD).ok_or(Error::CalculationFailed)?;
+        exp >>= 1;
+    }
+    Ok(result)
+}
+
+/// 每次抽取命中概率为 a/n、共m次独立抽取时至少命中一次的概率（WAD定点数）
+fn hit_at_least_once_wad(n: usize, a: usize, m: u128) -> Result<u128> {
+    let miss = mul_div((n - a) as u128, WAD, n as u128).ok_or(Error::CalculationFailed)?;
+    Ok(WAD - pow_wad(miss, m)?)
+}
+
+fn report(honest_wad: u128, achievable_wad: u128, outcomes: u128) -> BiasReport {
+    let honest = honest_wad / (WAD / BIAS_SCALE);
+    let achievable = achievable_wad / (WAD / BIAS_SCALE);
+    BiasReport {
+        honest_probability: honest,
+        achievable_probability: achievable,
+        bias: achievable.saturating_sub(honest),
+        outcomes,
+    }
+}
+
+/// 解析计算联盟使目标参与者被选中的最大概率。
+///
+/// 诚实情况下，`get_k_dd_rand_num`的过程在循环移位下不变，每个参与者被选中的概率为k/n。
+/// 只要至少有一位诚实参与者，各策略的最优结果为：
+///
+/// * `ChooseAfterReveal`：联盟非空时概率为1
+/// * `SelectiveReveal`：联盟在每个位置上承诺线性无关的低位（成员m承诺 2^m），
+///   2^c种揭示组合使每个位置的偏移量覆盖 a = min(2^c, n) 个值，
+///   概率为 1 - (1 - a/n)^k（k = 1时精确；k > 1时忽略线性探测的冲突，由模拟给出实际值）
+/// * `AbortAndRetry`：c+1次独立抽取，概率为 1 - (1 - k/n)^(c+1)
+///
+/// 联盟包含全部n位参与者时，任何策略下都可以完全控制结果。
+///
+/// # 参数
+///
+/// * `n` - 参与者数量（必须是2的n次幂）
+/// * `k` - 选择数量（0 < k <= n）
+/// * `coalition` - 联盟人数（<= n）
+/// * `strategy` - 联盟策略
+///
+/// # 返回值
+///
+/// * `Result<BiasReport>` - 成功时返回偏差分析报告
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{analyze_coalition_bias, CoalitionStrategy, BIAS_SCALE};
+///
+/// // 64人中选1人：诚实概率1/64；3人联盟选择性揭示可覆盖8个偏移量
+/// let r = analyze_coalition_bias(64, 1, 3, CoalitionStrategy::SelectiveReveal).unwrap();
+/// assert_eq!(r.honest_probability, BIAS_SCALE / 64);
+/// assert_eq!(r.achievable_probability, BIAS_SCALE / 8);
+/// assert_eq!(r.outcomes, 8);
+///
+/// // 6人联盟即可完全控制
+/// let r = analyze_coalition_bias(64, 1, 6, CoalitionStrategy::SelectiveReveal).unwrap();
+/// assert_eq!(r.achievable_probability, BIAS_SCALE);
+/// ```
+pub fn analyze_coalition_bias(
+    n: usize,
+    k: usize,
+    coalition: usize,
+    strategy: CoalitionStrategy,
+) -> Result<BiasReport> {
+    validate(n, k, coalition)?;
+    let honest_wad = mul_div(k as u128, WAD, n as u128).ok_or(Error::CalculationFailed)?;
+
+    if coalition == 0 {
+        return Ok(report(honest_wad, honest_wad, 1));
+    }
+    if coalition == n || strategy == CoalitionStrategy::ChooseAfterReveal {
+        return Ok(report(honest_wad, WAD, n as u128));
+    }
+
+    if strategy == CoalitionStrategy::SelectiveReveal {
+        let outcomes = if coalition >= 128 {
+            u128::MAX
+        } else {
+            1u128 << coalition
+        };
+        let covered = if outcomes >= n as u128 {
+            n
+        } else {
+            outcomes as usize
+        };
+        let achievable = hit_at_least_once_wad(n, covered, k as u128)?;
+        Ok(report(honest_wad, achievable, outcomes))
+    } else {
+        let attempts = coalition as u128 + 1;
+        let achievable = hit_at_least_once_wad(n, k, attempts)?;
+        Ok(report(honest_wad, achievable, attempts))
+    }
+}
+
+/// 使用真实的`get_k_dd_rand_num`模拟联盟使目标参与者被选中的概率。
+///
+/// 联盟由参与者0..coalition组成，目标为最后一位参与者n-1（不在联盟中时即为诚实参与者）。
+/// 诚实参与者的值由`seed`以计数器模式派生，每次试验：
+///
+/// * 诚实基线：所有人如实提交，统计目标被选中的次数
+/// * `ChooseAfterReveal`：联盟成员0将第一个值设为使异或结果指向目标的值
+/// * `SelectiveReveal`：成员m在每个位置承诺 2^m，枚举2^c种揭示组合（被扣留成员的值按0计），
+///   任一组合选中目标即成功
+/// * `AbortAndRetry`：进行最多c+1次独立抽取，任一次选中目标即成功
+///
+/// # 参数
+///
+/// * `n` - 参与者数量（必须是2的n次幂且 <= 64）
+/// * `k` - 选择数量（0 < k <= min(n, 16)）
+/// * `coalition` - 联盟人数（< n；`SelectiveReveal`时 <= 10）
+/// * `strategy` - 联盟策略
+/// * `seed` - 模拟种子
+/// * `trials` - 试验次数（必须 > 0）
+///
+/// # 返回值
+///
+/// * `Result<BiasReport>` - 成功时返回模拟得到的偏差分析报告
+pub fn simulate_coalition_bias(
+    n: usize,
+    k: usize,
+    coalition: usize,
+    strategy: CoalitionStrategy,
+    seed: u128,
+    trials: usize,
+) -> Result<BiasReport> {
+    validate(n, k, coalition)?;
+    if n > MAX_BIAS_SIM_PARTICIPANTS || k > MAX_BIAS_SIM_K || coalition >= n || trials == 0 {
+        return Err(Error::InvalidInput);
+    }
+    if strategy == CoalitionStrategy::SelectiveReveal && coalition > MAX_BIAS_SIM_SELECTIVE {
+        return Err(Error::InvalidInput);
+    }
+    let target = n - 1;
+
+    let mut values = [[0u128; MAX_BIAS_SIM_K]; MAX_BIAS_SIM_PARTICIPANTS];
+    let mut counter = 0u128;
+    let mut honest_hits = 0u128;
+    let mut coalition_hits = 0u128;
+
+    for _ in 0..trials {
+        fill(&mut values, n, k, seed, &mut counter);
+        let honest = selects_target(&values, n, k, target)?;
+        if honest {
+            honest_hits += 1;
+        }
+
+        let success = match strategy {
+            _ if coalition == 0 => honest,
+            CoalitionStrategy::ChooseAfterReveal => {
+                // 成员0最后提交：令第一个位置的异或结果等于目标
+                let mut others = 0u128;
+                for row in &values[1..n] {
+                    others ^= row[0];
+                }
+                values[0][0] = others ^ target as u128;
+                selects_target(&values, n, k, target)?
+            }
+            CoalitionStrategy::SelectiveReveal => {
+                let mut any = false;
+                for mask in 0..(1usize << coalition) {
+                    for (m, row) in values.iter_mut().enumerate().take(coalition) {
+                        let v = if (mask >> m) & 1 == 1 { 1u128 << m } else { 0 };
+                        row.iter_mut().for_each(|x| *x = v);
+                    }
+                    if selects_target(&values, n, k, target)? {
+                        any = true;
+                        break;
+                    }
+                }
+                any
+            }
+            CoalitionStrategy::AbortAndRetry => {
+                let mut any = honest;
+                for _ in 0..coalition {
+                    if any {
+                        break;
+                    }
+                    fill(&mut values, n, k, seed, &mut counter);
+                    any = selects_target(&values, n, k, target)?;
+                }
+                any
+            }
+        };
+        if success {
+            coalition_hits += 1;
+        }
+    }
+
+    let outcomes = match strategy {
+        _ if coalition == 0 => 1,
+        CoalitionStrategy::ChooseAfterReveal => n as u128,
+        CoalitionStrategy::SelectiveReveal => 1u128 << coalition,
+        CoalitionStrategy::AbortAndRetry => coalition as u128 + 1,
+    };
+    let t = trials as u128;
+    let honest_wad = mul_div(honest_hits, WAD, t).ok_or(Error::CalculationFailed)?;
+    let achievable_wad = mul_div(coalition_hits, WAD, t).ok_or(Error::CalculationFailed)?;
+    Ok(report(honest_wad, achievable_wad, outcomes))
+}
+
+fn fill(
+    values: &mut [[u128; MAX_BIAS_SIM_K]; MAX_BIAS_SIM_PARTICIPANTS],
+    n: usize,
+    k: usize,
+    seed: u128,
+    counter: &mut u128,
+) {
+    for row in values.iter_mut().take(n) {
+        for v in row.iter_mut().take(k) {
+            *v = mix_u128(seed, *counter);
+            *counter += 1;
+        }
+    }
+}
+
+fn selects_target(
+    values: &[[u128; MAX_BIAS_SIM_K]; MAX_BIAS_SIM_PARTICIPANTS],
+    n: usize,
+    k: usize,
+    target: usize,
+) -> Result<bool> {
+    let mut groups: [&[u128]; MAX_BIAS_SIM_PARTICIPANTS] = [&[]; MAX_BIAS_SIM_PARTICIPANTS];
+    for (g, row) in groups.iter_mut().zip(values.iter()).take(n) {
+        *g = &row[..k];
+    }
+    let mut output = [0usize; MAX_BIAS_SIM_K];
+    get_k_dd_rand_num(&groups[..n], n, k, &mut output[..k])?;
+    Ok(output[..k].contains(&target))
+}
@@ -0,0 +1,414 @@
+//! 参与式预算（participatory budgeting）规则。
+//!
+//! 在固定预算下根据带权重的赞成票选择资助项目，并报告每位投票者
+//! 承担的预算。所有计算都使用精确整数运算，结果在各节点间完全一致。
+
+use core::cmp::Ordering;
+
+use crate::math::{cmp_mul3, mul_div};
+use crate::types::VotingPower;
+use crate::{Error, Result};
+
+/// 最大项目数
+pub const MAX_PB_PROJECTS: usize = 256;
+
+/// 最大投票者数（相同选票应合并并累加权重）
+pub const MAX_PB_VOTERS: usize = 1_000;
+
+const PROJECT_WORDS: usize = MAX_PB_PROJECTS / 64;
+
+/// 参与式预算规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetingRule {
+    /// 贪心功利主义（背包投票）：按赞成权重从高到低资助放得下的项目
+    GreedyUtilitarian,
+    /// 等份额法（Method of Equal Shares）：预算按权重平分给投票者，项目由支持者按等价格分摊
+    EqualShares,
+    /// 等份额法，剩余预算再按贪心功利主义补全
+    EqualSharesWithCompletion,
+    /// 序贯Phragmén法：支持者按权重持续获得资金，最先凑足成本的项目被资助
+    Phragmen,
+}
+
+/// 根据赞成票在预算内选择资助项目。
+///
+/// 每位投票者的花费以整数报告，且每个被资助项目的成本在其支持者之间
+/// 精确分摊（花费之和恰好等于已资助项目的总成本）。
+///
+/// # 平局处理
+///
+/// 赞成权重（或等份额价格、Phragmén时间）相同时，成本低者优先，再相同则索引小者优先。
+///
+/// # 参数
+///
+/// * `costs` - 每个项目的成本（必须 > 0，项目数 <= 256）
+/// * `budget` - 总预算
+/// * `ballots` - 每位投票者赞成的项目索引（不可重复）
+/// * `weights` - 每位投票者的投票权重（应与ballots.len()匹配，投票者数 <= 1,000）
+/// * `rule` - 参与式预算规则
+/// * `funded` - 用于标记每个项目是否被资助的可变切片（应与costs.len()匹配）
+/// * `spent` - 用于存储每位投票者花费的可变切片（应与ballots.len()匹配）
+///
+/// # 返回值
+///
+/// * `Result<u128>` - 成功时返回已资助项目的总成本
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{calculate_participatory_budget, BudgetingRule};
+///
+/// let costs = [60u128, 40, 50];
+/// let v0 = [0usize, 1];
+/// let v1 = [0usize];
+/// let v2 = [2usize];
+/// let ballots = [v0.as_slice(), v1.as_slice(), v2.as_slice()];
+/// let weights = [1u128, 1, 1];
+/// let mut funded = [false; 3];
+/// let mut spent = [0u128; 3];
+/// let total = calculate_participatory_budget(
+///     &costs, 100, &ballots, &weights, BudgetingRule::GreedyUtilitarian, &mut funded, &mut spent,
+/// ).unwrap();
+/// assert_eq!(funded, [true, true, false]);
+/// assert_eq!(total, 100);
+/// assert_eq!(spent.iter().sum::<u128>(), total);
+/// ```
+pub fn calculate_participatory_budget(
+    costs: &[u128],
+    budget: u128,
+    ballots: &[&[usize]],
+    weights: &[VotingPower],
+    rule: BudgetingRule,
+    funded: &mut [bool],
+    spent: &mut [u128],
+) -> Result<u128> {
+    // 验证输入参数
+    if costs.is_empty() || costs.len() > MAX_PB_PROJECTS || funded.len() != costs.len() {
+        return Err(Error::InvalidInput);
+    }
+    if ballots.is_empty()
+        || ballots.len() > MAX_PB_VOTERS
+        || weights.len() != ballots.len()
+        || spent.len() != ballots.len()
+    {
+        return Err(Error::InvalidInput);
+    }
+    if costs.contains(&0) {
+        return Err(Error::InvalidInput);
+    }
+
+    // 将赞成票转换为位集，同时检查索引有效且不重复
+    let mut approvals = [[0u64; PROJECT_WORDS]; MAX_PB_VOTERS];
+    for (v, ballot) in ballots.iter().enumerate() {
+        for &p in ballot.iter() {
+            if p >= costs.len() || approvals[v][p / 64] & (1 << (p % 64)) != 0 {
+                return Err(Error::InvalidInput);
+            }
+            approvals[v][p / 64] |= 1 << (p % 64);
+        }
+    }
+
+    let mut scores = [0u128; MAX_PB_PROJECTS];
+    for (v, &w) in weights.iter().enumerate() {
+        for (p, score) in scores.iter_mut().enumerate().take(costs.len()) {
+            if approves(&approvals, v, p) {
+                *score = score.checked_add(w).ok_or(Error::CalculationFailed)?;
+            }
+        }
+    }
+
+    funded.iter_mut().for_each(|f| *f = false);
+    spent.iter_mut().for_each(|s| *s = 0);
+
+    let ctx = Profile {
+        costs,
+        weights,
+        approvals: &approvals,
+        scores: &scores,
+    };
+
+    let mut total = match rule {
+        BudgetingRule::GreedyUtilitarian => 0,
+        BudgetingRule::EqualShares | BudgetingRule::EqualSharesWithCompletion => {
+            ctx.equal_shares(budget, funded, spent)?
+        }
+        BudgetingRule::Phragmen => ctx.phragmen(budget, funded, spent)?,
+    };
+
+    if matches!(
+        rule,
+        BudgetingRule::GreedyUtilitarian | BudgetingRule::EqualSharesWithCompletion
+    ) {
+        total += ctx.greedy(budget - total, funded, spent)?;
+    }
+
+    Ok(total)
+}
+
+#[inline]
+fn approves(
+    approvals: &[[u64; PROJECT_WORDS]; MAX_PB_VOTERS],
+    voter: usize,
+    project: usize,
+) -> bool {
+    approvals[voter][project / 64] & (1 << (project % 64)) != 0
+}
+
+/// 投票档案及预计算的赞成权重
+struct Profile<'a> {
+    costs: &'a [u128],
+    weights: &'a [VotingPower],
+    approvals: &'a [[u64; PROJECT_WORDS]; MAX_PB_VOTERS],
+    scores: &'a [u128; MAX_PB_PROJECTS],
+}
+
+impl Profile<'_> {
+    /// 项目p的支持者（权重为0的投票者不计入）
+    fn supports(&self, voter: usize, project: usize) -> bool {
+        self.weights[voter] > 0 && approves(self.approvals, voter, project)
+    }
+
+    /// 得分高者优先，其次成本低者，再次索引小者
+    fn better_score(&self, a: usize, b: usize) -> bool {
+        match self.scores[a].cmp(&self.scores[b]) {
+            Ordering::Greater => true,
+            Ordering::Less => false,
+            Ordering::Equal => self.costs[a] < self.costs[b],
+        }
+    }
+
+    /// 按赞成权重从高到低资助剩余预算内放得下的项目，成本按支持者权重比例分摊
+    fn greedy(&self, mut remaining: u128, funded: &mut [bool], spent: &mut [u128]) -> Result<u128> {
+        let mut considered = [false; MAX_PB_PROJECTS];
+        let mut total = 0u128;
+        loop {
+            let mut best: Option<usize> = None;
+            for p in 0..self.costs.len() {
+                if funded[p] || considered[p] || self.scores[p] == 0 {
+                    continue;
+                }
+                match best {
+                    Some(b) if !self.better_score(p, b) => {}
+                    _ => best = Some(p),
+                }
+            }
+            let Some(p) = best else {
+                break;
+            };
+            considered[p] = true;
+            if self.costs[p] > remaining {
+                continue;
+            }
+
+            funded[p] = true;
+            remaining -= self.costs[p];
+            total += self.costs[p];
+
+            let score = self.scores[p];
+            let mut paid = 0u128;
+            for (v, s) in spent.iter_mut().enumerate() {
+                if self.supports(v, p) {
+                    let share = mul_div(self.costs[p], self.weights[v], score)
+                        .ok_or(Error::CalculationFailed)?;
+                    *s += share;
+                    paid += share;
+                }
+            }
+            self.settle_rounding(p, self.costs[p] - paid, spent);
+        }
+        Ok(total)
+    }
+
+    /// 把取整产生的剩余成本逐个单位分给支持者（按索引顺序）
+    fn settle_rounding(&self, project: usize, mut leftover: u128, spent: &mut [u128]) {
+        while leftover > 0 {
+            for (v, s) in spent.iter_mut().enumerate() {
+                if leftover > 0 && self.supports(v, project) {
+                    *s += 1;
+                    leftover -= 1;
+                }
+            }
+        }
+    }
+
+    fn equal_shares(&self, budget: u128, funded: &mut [bool], spent: &mut [u128]) -> Result<u128> {
+        let num_voters = self.weights.len();
+        let mut total_weight = 0u128;
+        for &w in self.weights {
+            total_weight = total_weight
+                .checked_add(w)
+                .ok_or(Error::CalculationFailed)?;
+        }
+        if total_weight == 0 {
+            return Err(Error::InvalidInput);
+        }
+
+        // 每位投票者的初始预算份额
+        let mut share = [0u128; MAX_PB_VOTERS];
+        for (s, &w) in share.iter_mut().zip(self.weights) {
+            *s = mul_div(budget, w, total_weight).ok_or(Error::CalculationFailed)?;
+        }
+
+        let mut total = 0u128;
+        loop {
+            // 寻找单位价格 rho = num / den 最低的可负担项目
+            let mut best: Option<(usize, u128, u128)> = None;
+            for (p, &done) in funded.iter().enumerate() {
+                if done || self.scores[p] == 0 {
+                    continue;
+                }
+                let Some((num, den)) = self.equal_shares_price(p, &share)? else {
+                    continue;
+                };
+                let better = match best {
+                    None => true,
+                    Some((b, bn, bd)) => match cmp_mul3(num, bd, 1, bn, den, 1) {
+                        Ordering::Less => true,
+                        Ordering::Greater => false,
+                        Ordering::Equal => self.better_score(p, b),
+                    },
+                };
+                if better {
+                    best = Some((p, num, den));
+                }
+            }
+            let Some((p, num, den)) = best else {
+                break;
+            };
+
+            // 每位支持者支付 min(剩余份额, rho * 权重)
+            funded[p] = true;
+            total += self.costs[p];
+            let mut paid = 0u128;
+            let mut capped = [false; MAX_PB_VOTERS];
+            for v in 0..num_voters {
+                if !self.supports(v, p) {
+                    continue;
+                }
+                let due = mul_div(num, self.weights[v], den).ok_or(Error::CalculationFailed)?;
+                let pay = if due >= share[v] {
+                    capped[v] = true;
+                    share[v]
+                } else {
+                    due
+                };
+                share[v] -= pay;
+                spent[v] += pay;
+                paid += pay;
+            }
+
+            // 取整产生的剩余成本由未封顶的支持者逐个单位补足
+            let mut leftover = self.costs[p] - paid;
+            while leftover > 0 {
+                let mut progressed = false;
+                for v in 0..num_voters {
+                    if leftover > 0 && self.supports(v, p) && !capped[v] && share[v] > 0 {
+                        share[v] -= 1;
+                        spent[v] += 1;
+                        leftover -= 1;
+                        progressed = true;
+                    }
+                }
+                if !progressed {
+                    return Err(Error::CalculationFailed);
+                }
+            }
+        }
+        Ok(total)
+    }
+
+    /// 计算项目在等份额法下的单位价格 rho（以分数 num/den 表示），不可负担时返回None
+    fn equal_shares_price(
+        &self,
+        project: usize,
+        share: &[u128; MAX_PB_VOTERS],
+    ) -> Result<Option<(u128, u128)>> {
+        let mut supporters = [0usize; MAX_PB_VOTERS];
+        let mut count = 0usize;
+        let mut available = 0u128;
+        let mut weight = 0u128;
+        for (v, &s) in share.iter().enumerate().take(self.weights.len()) {
+            if self.supports(v, project) {
+                supporters[count] = v;
+                count += 1;
+                available += s;
+                weight += self.weights[v];
+            }
+        }
+        if count == 0 || available < self.costs[project] {
+            return Ok(None);
+        }
+
+        // 按 份额/权重 从小到大排序，份额不足的支持者支付全部剩余份额
+        let list = &mut supporters[..count];
+        list.sort_unstable_by(|&a, &b| {
+            cmp_mul3(share[a], self.weights[b], 1, share[b], self.weights[a], 1).then(a.cmp(&b))
+        });
+
+        let mut rem_cost = self.costs[project];
+        let mut rem_weight = weight;
+        for &v in list.iter() {
+            if cmp_mul3(share[v], rem_weight, 1, rem_cost, self.weights[v], 1) != Ordering::Less {
+                return Ok(Some((rem_cost, rem_weight)));
+            }
+            rem_cost -= share[v];
+            rem_weight -= self.weights[v];
+        }
+        Err(Error::CalculationFailed)
+    }
+
+    fn phragmen(&self, budget: u128, funded: &mut [bool], spent: &mut [u128]) -> Result<u128> {
+        let mut total = 0u128;
+        loop {
+            // 项目p可负担的时间 t = (成本 + 支持者已花费) / 支持者权重
+            let mut best: Option<(usize, u128, u128)> = None;
+            for (p, &done) in funded.iter().enumerate() {
+                if done || self.scores[p] == 0 {
+                    continue;
+                }
+                let mut num = self.costs[p];
+                for (v, &s) in spent.iter().enumerate() {
+                    if self.supports(v, p) {
+                        num = num.checked_add(s).ok_or(Error::CalculationFailed)?;
+                    }
+                }
+                let den = self.scores[p];
+                let better = match best {
+                    None => true,
+                    Some((b, bn, bd)) => match cmp_mul3(num, bd, 1, bn, den, 1) {
+                        Ordering::Less => true,
+                        Ordering::Greater => false,
+                        Ordering::Equal => self.better_score(p, b),
+                    },
+                };
+                if better {
+                    best = Some((p, num, den));
+                }
+            }
+            let Some((p, num, den)) = best else {
+                break;
+            };
+            // 最早可负担的项目超出剩余预算时停止
+            if self.costs[p] > budget - total {
+                break;
+            }
+
+            funded[p] = true;
+            total += self.costs[p];
+
+            // 每位支持者的累计花费提升到 权重 * t
+            let mut paid = 0u128;
+            for (v, s) in spent.iter_mut().enumerate() {
+                if self.supports(v, p) {
+                    let target =
+                        mul_div(self.weights[v], num, den).ok_or(Error::CalculationFailed)?;
+                    let delta = target.saturating_sub(*s).min(self.costs[p] - paid);
+                    *s += delta;
+                    paid += delta;
+                }
+            }
+            self.settle_rounding(p, self.costs[p] - paid, spent);
+        }
+        Ok(total)
+    }
+}
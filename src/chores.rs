@@ -0,0 +1,190 @@
+//! 家务（负效用物品）的公平分配。
+//!
+//! 与商品相反，家务（如值班、代码评审）是人人都不想要的负担：
+//!
+//! * `calculate_chore_division_equal_weights` / `calculate_chore_division_weighted` -
+//!   单项家务的超级公平补偿：报价（要求的补偿）最低者承担，由其他人付费补偿，
+//!   补偿之和为0（与`calculate_fair_division_*`共用同一计算，保证零和）
+//! * `round_robin_chores` - 多项家务的轮流挑选，结果满足家务意义下的EF1
+//! * `is_ef1_chores` - 检查家务分配是否满足EF1
+
+use crate::algorithms::super_fair_in_place;
+use crate::assignment::{
+    resolve_order, validate_valuations, MAX_ALLOCATION_AGENTS, MAX_ASSIGNMENT_SIZE,
+};
+use crate::{Error, Result};
+
+/// 将报价取反写入`output`，作为商品的出价
+fn negate_asks(asks: &[i128], output: &mut [i128]) -> Result<()> {
+    if asks.is_empty() || output.len() != asks.len() {
+        return Err(Error::InvalidInput);
+    }
+    if asks.len() < 2 {
+        return Err(Error::NotEnoughParticipants);
+    }
+    for (o, &a) in output.iter_mut().zip(asks) {
+        *o = a.checked_neg().ok_or(Error::CalculationFailed)?;
+    }
+    Ok(())
+}
+
+/// 报价最低者的索引（相同时取索引小者）
+fn lowest_ask(asks: &[i128]) -> usize {
+    let mut best = 0;
+    for (i, &a) in asks.iter().enumerate() {
+        if a < asks[best] {
+            best = i;
+        }
+    }
+    best
+}
+
+/// 为权重相等的参与者计算单项家务的超级公平补偿。
+///
+/// 每位参与者报出自己愿意承担该家务所要求的补偿。把报价取反视为对"商品"的出价，
+/// 按`calculate_fair_division_equal_weights`计算：出价最高即报价最低者承担家务，
+/// 其余参与者的结果为 -报价/n + delta（通常为负，即付费），承担者获得其他人结果之和的相反数，
+/// 所有结果之和恰好为0。取反后的报价直接写入`output`并原地计算，不需要额外的缓冲区。
+///
+/// # 参数
+///
+/// * `asks` - 每位参与者要求的补偿（参与者数 >= 2）
+/// * `output` - 用于存储每位参与者净收入（正为收款，负为付款）的可变切片
+///
+/// # 返回值
+///
+/// * `Result<usize>` - 成功时返回承担家务的参与者索引
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::calculate_chore_division_equal_weights;
+///
+/// let asks = [30i128, 10, 20];
+/// let mut output = [0i128; 3];
+/// let assignee = calculate_chore_division_equal_weights(&asks, &mut output).unwrap();
+/// assert_eq!(assignee, 1);
+/// assert_eq!(output.iter().sum::<i128>(), 0);
+/// // 参与者0和2付费，参与者1收款
+/// assert_eq!(output, [-7, 10, -3]);
+/// ```
+pub fn calculate_chore_division_equal_weights(asks: &[i128], output: &mut [i128]) -> Result<usize> {
+    negate_asks(asks, output)?;
+    super_fair_in_place(output, None)?;
+    Ok(lowest_ask(asks))
+}
+
+/// 为权重不同的参与者计算单项家务的超级公平补偿。
+///
+/// 与`calculate_chore_division_equal_weights`相同，但按`calculate_fair_division_weighted`
+/// 的方式按权重分摊。
+///
+/// # 参数
+///
+/// * `asks` - 每位参与者要求的补偿（参与者数 >= 2）
+/// * `weights` - 每位参与者的权重（必须 > 0）
+/// * `output` - 用于存储每位参与者净收入（正为收款，负为付款）的可变切片
+///
+/// # 返回值
+///
+/// * `Result<usize>` - 成功时返回承担家务的参与者索引
+pub fn calculate_chore_division_weighted(
+    asks: &[i128],
+    weights: &[i128],
+    output: &mut [i128],
+) -> Result<usize> {
+    if weights.len() != asks.len() || weights.iter().any(|&w| w <= 0) {
+        return Err(Error::InvalidInput);
+    }
+    negate_asks(asks, output)?;
+    super_fair_in_place(output, Some(weights))?;
+    Ok(lowest_ask(asks))
+}
+
+/// 轮流挑选分配多项家务，结果满足EF1。
+///
+/// 参与者按挑选顺序轮流领走自己成本最低的剩余家务（成本相同时取索引小者），
+/// 直到家务分完。挑选顺序可以由`get_k_dd_rand_num`（k = n）的输出随机化；
+/// 排在前面的参与者可能多领一项家务。
+///
+/// # 参数
+///
+/// * `costs` - 每位参与者对每项家务的可加成本（参与者数 <= 32，每行长度为家务数m）
+/// * `order` - 挑选顺序（0..n的排列），None表示按索引顺序
+/// * `allocation` - 用于存储每项家务承担者的可变切片（长度为m，m <= 256）
+///
+/// # 返回值
+///
+/// * `Result<()>` - 成功时返回Ok，输入无效时返回InvalidInput
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{is_ef1_chores, round_robin_chores};
+///
+/// let c0 = [1u128, 5, 3, 8]; // 值班、评审……的成本
+/// let c1 = [2u128, 2, 9, 1];
+/// let costs = [c0.as_slice(), c1.as_slice()];
+/// let mut allocation = [0usize; 4];
+/// round_robin_chores(&costs, None, &mut allocation).unwrap();
+/// assert_eq!(allocation, [0, 1, 0, 1]);
+/// assert!(is_ef1_chores(&costs, &allocation).unwrap());
+/// ```
+pub fn round_robin_chores(
+    costs: &[&[u128]],
+    order: Option<&[usize]>,
+    allocation: &mut [usize],
+) -> Result<()> {
+    let (n, m) = validate_valuations(costs, allocation)?;
+    let mut sequence = [0usize; MAX_ALLOCATION_AGENTS];
+    resolve_order(order, n, &mut sequence)?;
+
+    let mut taken = [false; MAX_ASSIGNMENT_SIZE];
+    for turn in 0..m {
+        let agent = sequence[turn % n];
+        let row = costs[agent];
+        let mut best = usize::MAX;
+        for chore in 0..m {
+            if !taken[chore] && (best == usize::MAX || row[chore] < row[best]) {
+                best = chore;
+            }
+        }
+        taken[best] = true;
+        allocation[best] = agent;
+    }
+    Ok(())
+}
+
+/// 检查家务分配是否满足EF1。
+///
+/// 对任意参与者i、j，去掉i自己承担的成本最高的一项家务后，i的总成本不超过
+/// i眼中j的总成本：c_i(B_i) - max_{g∈B_i} c_i(g) <= c_i(B_j)。
+///
+/// # 参数
+///
+/// * `costs` - 每位参与者对每项家务的可加成本（参与者数 <= 32）
+/// * `allocation` - 每项家务的承担者
+///
+/// # 返回值
+///
+/// * `Result<bool>` - 满足EF1时返回true
+pub fn is_ef1_chores(costs: &[&[u128]], allocation: &[usize]) -> Result<bool> {
+    let (n, _) = validate_valuations(costs, allocation)?;
+    if allocation.iter().any(|&a| a >= n) {
+        return Err(Error::InvalidInput);
+    }
+    for (i, row) in costs.iter().enumerate() {
+        let mut bundles = [0u128; MAX_ALLOCATION_AGENTS];
+        let mut own_max = 0u128;
+        for (&c, &a) in row.iter().zip(allocation) {
+            bundles[a] = bundles[a].checked_add(c).ok_or(Error::CalculationFailed)?;
+            if a == i {
+                own_max = own_max.max(c);
+            }
+        }
+        if (0..n).any(|j| bundles[i] - own_max > bundles[j]) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
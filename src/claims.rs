@@ -0,0 +1,256 @@
+//! 破产（索赔）问题的分配规则。
+//!
+//! 金库或清算池不足以偿付全部索赔时，按公认的规则在索赔人之间分配遗产（estate）。
+//! 所有规则的结果都是整数，每人所得不超过其索赔额，且总和恰好等于遗产。
+//! 精确结果不是整数时先向下取整，剩余的单位按小数部分从大到小分配
+//! （小数部分相同时索引小者优先），每人最多多得1个单位；按损失计算的规则
+//! （约束等额损失、遗产超过半数时的塔木德规则）对损失做同样的舍入。
+
+use crate::math::mul_div_rem;
+use crate::{Error, Result};
+
+/// 最大索赔人数
+pub const MAX_CLAIMANTS: usize = 1_000;
+
+/// 随机到达规则支持的最大索赔人数（需要枚举 2^(n-1) 个子集）
+pub const MAX_RANDOM_ARRIVAL_CLAIMANTS: usize = 16;
+
+/// 索赔问题的分配规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaimsRule {
+    /// 按索赔额比例分配
+    Proportional,
+    /// 约束等额奖励（CEA）：每人得 min(索赔额, λ)，λ使总和等于遗产
+    ConstrainedEqualAwards,
+    /// 约束等额损失（CEL）：每人得 max(0, 索赔额 - μ)，μ使总和等于遗产
+    ConstrainedEqualLosses,
+    /// 塔木德规则（争衣原则）：遗产不超过总索赔一半时对半索赔额用CEA，
+    /// 否则每人先得半索赔额，损失再对半索赔额用CEA
+    Talmud,
+    /// 随机到达规则：对所有到达顺序的先到先得结果取平均（索赔人数 <= 16）
+    RandomArrival,
+}
+
+/// 在索赔人之间分配遗产。
+///
+/// # 参数
+///
+/// * `claims` - 每位索赔人的索赔额（索赔人数 <= 1,000）
+/// * `estate` - 待分配的遗产（必须 <= 索赔总额）
+/// * `rule` - 分配规则
+/// * `awards` - 用于存储每位索赔人所得的可变切片（应与claims.len()匹配）
+///
+/// # 返回值
+///
+/// * `Result<()>` - 成功时返回Ok；输入无效或遗产超过索赔总额时返回InvalidInput，
+///   数值溢出时返回CalculationFailed
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{calculate_claims_division, ClaimsRule};
+///
+/// // 《塔木德》中的经典例子：索赔100、200、300
+/// let claims = [100u128, 200, 300];
+/// let mut awards = [0u128; 3];
+/// calculate_claims_division(&claims, 200, ClaimsRule::Talmud, &mut awards).unwrap();
+/// assert_eq!(awards, [50, 75, 75]);
+/// calculate_claims_division(&claims, 300, ClaimsRule::Talmud, &mut awards).unwrap();
+/// assert_eq!(awards, [50, 100, 150]);
+///
+/// calculate_claims_division(&claims, 300, ClaimsRule::ConstrainedEqualLosses, &mut awards)
+///     .unwrap();
+/// assert_eq!(awards, [0, 100, 200]);
+/// ```
+pub fn calculate_claims_division(
+    claims: &[u128],
+    estate: u128,
+    rule: ClaimsRule,
+    awards: &mut [u128],
+) -> Result<()> {
+    // 验证输入参数
+    let n = claims.len();
+    if n == 0 || n > MAX_CLAIMANTS || awards.len() != n {
+        return Err(Error::InvalidInput);
+    }
+    let mut total = 0u128;
+    for &c in claims {
+        total = total.checked_add(c).ok_or(Error::CalculationFailed)?;
+    }
+    if estate > total {
+        return Err(Error::InvalidInput);
+    }
+
+    match rule {
+        ClaimsRule::Proportional => proportional(claims, estate, total, awards),
+        ClaimsRule::ConstrainedEqualAwards => {
+            constrained_equal_awards(claims, estate, awards);
+            Ok(())
+        }
+        ClaimsRule::ConstrainedEqualLosses => {
+            constrained_equal_awards(claims, total - estate, awards);
+            for (a, &c) in awards.iter_mut().zip(claims) {
+                *a = c - *a;
+            }
+            Ok(())
+        }
+        ClaimsRule::Talmud => talmud(claims, estate, total, awards),
+        ClaimsRule::RandomArrival => random_arrival(claims, estate, awards),
+    }
+}
+
+/// 剩余单位按余数从大到小分配（余数相同时索引小者优先），每人最多多得1个单位
+fn distribute_by_remainder(remainders: &[u128], mut leftover: u128, awards: &mut [u128]) {
+    let mut bonus = [false; MAX_CLAIMANTS];
+    while leftover > 0 {
+        let mut best: Option<usize> = None;
+        for (i, &r) in remainders.iter().enumerate() {
+            if bonus[i] || r == 0 {
+                continue;
+            }
+            let better = match best {
+                None => true,
+                Some(b) => r > remainders[b],
+            };
+            if better {
+                best = Some(i);
+            }
+        }
+        match best {
+            Some(b) => {
+                bonus[b] = true;
+                awards[b] += 1;
+                leftover -= 1;
+            }
+            None => break,
+        }
+    }
+}
+
+/// 比例规则
+fn proportional(claims: &[u128], estate: u128, total: u128, awards: &mut [u128]) -> Result<()> {
+    if total == 0 {
+        awards.iter_mut().for_each(|a| *a = 0);
+        return Ok(());
+    }
+    let mut remainders = [0u128; MAX_CLAIMANTS];
+    let mut allocated = 0u128;
+    for (i, &c) in claims.iter().enumerate() {
+        let (q, r) = mul_div_rem(c, estate, total).ok_or(Error::CalculationFailed)?;
+        awards[i] = q;
+        remainders[i] = r;
+        allocated += q;
+    }
+    distribute_by_remainder(&remainders[..claims.len()], estate - allocated, awards);
+    Ok(())
+}
+
+/// 约束等额奖励：amount <= Σclaims
+fn constrained_equal_awards(claims: &[u128], amount: u128, awards: &mut [u128]) {
+    let capped = |lambda: u128| -> u128 { claims.iter().map(|&c| c.min(lambda)).sum() };
+
+    // 最大的整数λ使 Σ min(c, λ) <= amount
+    let mut lo = 0u128;
+    let mut hi = claims.iter().copied().max().unwrap_or(0);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2 + 1;
+        if capped(mid) <= amount {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+
+    // 余下的单位（少于索赔额超过λ的人数）按索引顺序各加1
+    let mut leftover = amount - capped(lo);
+    for (a, &c) in awards.iter_mut().zip(claims) {
+        *a = c.min(lo);
+        if leftover > 0 && c > lo {
+            *a += 1;
+            leftover -= 1;
+        }
+    }
+}
+
+/// 对半索赔额做约束等额奖励：先在两倍单位下计算，再减半并分配奇数余下的单位
+fn half_claims_cea(claims: &[u128], amount: u128, awards: &mut [u128]) -> Result<()> {
+    let doubled = amount.checked_mul(2).ok_or(Error::CalculationFailed)?;
+    constrained_equal_awards(claims, doubled, awards);
+    let mut odd = 0u128;
+    let mut remainders = [0u128; MAX_CLAIMANTS];
+    for (a, r) in awards.iter_mut().zip(remainders.iter_mut()) {
+        *r = *a % 2;
+        odd += *r;
+        *a /= 2;
+    }
+    distribute_by_remainder(&remainders[..claims.len()], odd / 2, awards);
+    Ok(())
+}
+
+/// 塔木德规则
+fn talmud(claims: &[u128], estate: u128, total: u128, awards: &mut [u128]) -> Result<()> {
+    let losses = total - estate;
+    if estate <= losses {
+        half_claims_cea(claims, estate, awards)
+    } else {
+        half_claims_cea(claims, losses, awards)?;
+        for (a, &c) in awards.iter_mut().zip(claims) {
+            *a = c - *a;
+        }
+        Ok(())
+    }
+}
+
+/// 随机到达规则：a_i = Σ_{S ⊆ N\{i}} |S|!(n-1-|S|)!/n! · min(c_i, max(0, E - c(S)))
+fn random_arrival(claims: &[u128], estate: u128, awards: &mut [u128]) -> Result<()> {
+    let n = claims.len();
+    if n > MAX_RANDOM_ARRIVAL_CLAIMANTS {
+        return Err(Error::InvalidInput);
+    }
+    let mut factorial = [1u128; MAX_RANDOM_ARRIVAL_CLAIMANTS + 1];
+    for k in 1..=n {
+        factorial[k] = factorial[k - 1] * k as u128;
+    }
+    let orders = factorial[n];
+
+    let mut remainders = [0u128; MAX_RANDOM_ARRIVAL_CLAIMANTS];
+    let mut allocated = 0u128;
+    for i in 0..n {
+        // 其他索赔人
+        let mut others = [0usize; MAX_RANDOM_ARRIVAL_CLAIMANTS];
+        let mut count = 0usize;
+        for j in (0..n).filter(|&j| j != i) {
+            others[count] = j;
+            count += 1;
+        }
+
+        // 按格雷码顺序枚举子集，每步只加入或移除一人
+        let mut sum = 0u128;
+        let mut size = 0usize;
+        let mut quotient = 0u128;
+        let mut remainder = 0u128;
+        for step in 0..1u64 << count {
+            if step > 0 {
+                let bit = step.trailing_zeros() as usize;
+                let gray = step ^ (step >> 1);
+                if gray & (1 << bit) != 0 {
+                    sum += claims[others[bit]];
+                    size += 1;
+                } else {
+                    sum -= claims[others[bit]];
+                    size -= 1;
+                }
+            }
+            let received = claims[i].min(estate.saturating_sub(sum));
+            let weight = factorial[size] * factorial[n - 1 - size];
+            let (q, r) = mul_div_rem(weight, received, orders).ok_or(Error::CalculationFailed)?;
+            quotient += q;
+            remainder += r;
+        }
+        awards[i] = quotient + remainder / orders;
+        remainders[i] = remainder % orders;
+        allocated += awards[i];
+    }
+    distribute_by_remainder(&remainders[..n], estate - allocated, awards);
+    Ok(())
+}
@@ -11,11 +11,40 @@
 //! - Equal weights and weighted fair division
 //! - Super fair division algorithms
 //! - Optimal resource allocation
+//! - Indivisible item allocation without money (random serial dictatorship, probabilistic serial with BvN lottery)
+//! - EF1 allocation of indivisible goods (round-robin, envy-cycle elimination) with EF1/EFX checkers
+//! - Exact maximum Nash welfare allocation for small instances (branch and bound)
+//! - Chore division: super-fair compensation for a burden and EF1 round-robin for multiple chores
+//! - Bankruptcy (claims) rules: proportional, constrained equal awards/losses, Talmud and random arrival
 //!
 //! ### 🎲 Decentralized Random Number Generation
 //! - Single and multiple random number generation
 //! - Collision resistance and uniqueness guarantees
 //! - Offset mechanism for pattern prevention
+//! - Deterministic full shuffle of any length
+//! - Eligibility-restricted selection (inclusion or exclusion lists)
+//! - Stratified sortition with per-category seat quotas
+//! - Epoch-chained committee rotation with cooldowns (exclude or down-weight)
+//! - Chained randomness beacon with replay verification from genesis
+//! - Threshold (t-of-n) randomness with absentee policies and a missing report
+//! - Shamir secret sharing to recover withheld reveals (Feldman share checks; hiding Pedersen commitments over ristretto255 with the `pedersen` feature)
+//! - ECVRF-EDWARDS25519-SHA512-TAI verification with adapters to `values`/`groups` (`vrf` feature)
+//! - Large-scale sortition (n beyond 2^32, k up to n) in expected O(k) with a caller bitset
+//! - ChaCha20 stream (`DdRng`) seeded from one round of contributions, with optional `rand_core` support
+//! - Lottery draws (Fucai 3D, pick-5, 6-of-49) with prize-tier matching
+//! - Lottery settlement with fixed, pari-mutuel and rollover jackpot tiers
+//! - Verifiable draw transcripts with canonical encoding, SHA-256 digest and independent verifier
+//! - Statistical randomness tests (chi-square, Kolmogorov–Smirnov, serial correlation, selection frequency)
+//!
+//! ### 🗳️ Voting and Elections
+//! - Single Transferable Vote (Droop quota, Gregory or Meek transfers)
+//! - Seat apportionment (D'Hondt, Sainte-Laguë, Huntington–Hill, Hamilton)
+//! - Participatory budgeting (greedy knapsack, Method of Equal Shares, Phragmén)
+//! - Banzhaf and Shapley–Shubik voting power indices
+//!
+//! ### 📊 Decentralization Metrics
+//! - Nakamoto coefficient, Gini coefficient, HHI and Theil index
+//! - Coalition bias analysis for `get_k_dd_rand_num` (analytic and simulated)
 //!
 //! ## Quick Start
 //!
@@ -37,7 +66,34 @@
 
 // Core modules
 pub mod algorithms;
+pub mod apportionment;
+pub mod assignment;
+pub mod beacon;
+pub mod bias;
+pub mod budgeting;
+pub mod chores;
+pub mod claims;
+pub mod lottery;
+pub mod metrics;
+#[cfg(feature = "pedersen")]
+pub mod pedersen;
+pub mod power_index;
+pub mod rng;
+pub mod rotation;
+pub mod selection;
+pub mod settlement;
+pub mod shamir;
+pub mod statistics;
+pub mod stv;
+pub mod threshold;
+pub mod transcript;
 pub mod types;
+#[cfg(feature = "vrf")]
+pub mod vrf;
+
+// Internal helpers
+mod math;
+mod sha256;
 
 // Unit tests for algorithms live in `src/test.rs`
 #[cfg(test)]
@@ -45,7 +101,30 @@ mod test;
 
 // Re-export main functionality
 pub use algorithms::*;
+pub use apportionment::*;
+pub use assignment::*;
+pub use beacon::*;
+pub use bias::*;
+pub use budgeting::*;
+pub use chores::*;
+pub use claims::*;
+pub use lottery::*;
+pub use metrics::*;
+#[cfg(feature = "pedersen")]
+pub use pedersen::*;
+pub use power_index::*;
+pub use rng::*;
+pub use rotation::*;
+pub use selection::*;
+pub use settlement::*;
+pub use shamir::*;
+pub use statistics::*;
+pub use stv::*;
+pub use threshold::*;
+pub use transcript::*;
 pub use types::*;
+#[cfg(feature = "vrf")]
+pub use vrf::*;
 
 // Common error types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -56,6 +135,11 @@ pub enum Error {
     CalculationFailed,
     /// Not enough participants (minimum 2)
     NotEnoughParticipants,
+    /// A stratum (category) has fewer members than the seats it must fill
+    StratumTooSmall {
+        /// Index of the first stratum that cannot be filled
+        category: usize,
+    },
 }
 
 /// Result type for the library
@@ -0,0 +1,246 @@
+//! 彩票开奖与中奖匹配。
+//!
+//! 由所有参与者贡献值的异或结果派生开奖号码，支持福彩3D、排列5等数字型玩法
+//! 以及6/49等乐透型玩法，并提供福彩3D直选、组选三、组选六的中奖判定。
+
+use crate::rng::DdRng;
+use crate::{Error, Result};
+
+/// 乐透型玩法的最大号码池
+pub const MAX_LOTTERY_POOL: usize = 255;
+
+/// 彩票玩法格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LotteryFormat {
+    /// 数字型：每位0–9独立抽取，可重复（福彩3D为3位，排列5为5位）
+    Digits(usize),
+    /// 乐透型：从1..=pool中不放回抽取pick个号码，按升序输出（如6/49）
+    Pick {
+        /// 抽取的号码个数
+        pick: usize,
+        /// 号码池大小（必须 <= 255）
+        pool: usize,
+    },
+}
+
+impl LotteryFormat {
+    /// 福彩3D：三位0–9数字
+    pub const FUCAI_3D: LotteryFormat = LotteryFormat::Digits(3);
+    /// 排列5：五位0–9数字
+    pub const PICK_5: LotteryFormat = LotteryFormat::Digits(5);
+    /// 6/49乐透
+    pub const SIX_OF_49: LotteryFormat = LotteryFormat::Pick { pick: 6, pool: 49 };
+
+    /// 开奖号码的个数
+    pub fn draw_len(&self) -> usize {
+        match *self {
+            LotteryFormat::Digits(count) => count,
+            LotteryFormat::Pick { pick, .. } => pick,
+        }
+    }
+}
+
+/// 福彩3D投注方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Play3d {
+    /// 直选：号码与开奖号码按位完全相同
+    Straight,
+    /// 组选：号码与开奖号码相同，顺序不限
+    Box,
+}
+
+/// 福彩3D中奖等级
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prize3d {
+    /// 直选中奖
+    Straight,
+    /// 组选三中奖（开奖号码中有两位相同）
+    Group3,
+    /// 组选六中奖（开奖号码三位各不相同）
+    Group6,
+    /// 未中奖
+    NoPrize,
+}
+
+/// 由参与者贡献值生成彩票开奖号码。
+///
+/// 先按`get_one_dd_rand_num`对所有值进行异或得到种子，再以种子创建`DdRng`
+/// （ChaCha20随机数流，与`dd_shuffle`等函数相同），通过`DdRng::gen_below`的拒绝采样
+/// 得到无偏的开奖号码：
+///
+/// * 数字型：在 [0, 10^count) 内均匀抽取一个整数，按十进制逐位输出（高位在前）
+/// * 乐透型：用部分Fisher–Yates洗牌从1..=pool中不放回抽取，升序输出
+///
+/// # 参数
+///
+/// * `values` - 表示参与者输入数据的u128值切片
+/// * `n` - 输入数组中的值数量（应与values.len()匹配，且必须是2的n次幂）
+/// * `format` - 彩票玩法格式
+/// * `output` - 用于存储开奖号码的可变切片（长度应与`format.draw_len()`匹配）
+///
+/// # 返回值
+///
+/// * `Result<()>` - 成功时返回Ok，计算失败时返回Error
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{get_dd_lottery_draw, LotteryFormat};
+///
+/// let values = [100u128, 200, 300, 400];
+/// let mut digits = [0u8; 3];
+/// get_dd_lottery_draw(&values, 4, LotteryFormat::FUCAI_3D, &mut digits).unwrap();
+/// assert!(digits.iter().all(|&d| d <= 9));
+///
+/// let mut numbers = [0u8; 6];
+/// get_dd_lottery_draw(&values, 4, LotteryFormat::SIX_OF_49, &mut numbers).unwrap();
+/// assert!(numbers.windows(2).all(|w| w[0] < w[1]));
+/// ```
+pub fn get_dd_lottery_draw(
+    values: &[u128],
+    n: usize,
+    format: LotteryFormat,
+    output: &mut [u8],
+) -> Result<()> {
+    if output.len() != format.draw_len() || output.is_empty() {
+        return Err(Error::InvalidInput);
+    }
+
+    let mut rng = DdRng::from_contributions(values, n)?;
+
+    match format {
+        LotteryFormat::Digits(count) => {
+            let range = 10u128
+                .checked_pow(count as u32)
+                .ok_or(Error::InvalidInput)?;
+            let mut number = rng.gen_below(range);
+            for digit in output.iter_mut().rev() {
+                *digit = (number % 10) as u8;
+                number /= 10;
+            }
+        }
+        LotteryFormat::Pick { pick, pool } => {
+            if pool == 0 || pool > MAX_LOTTERY_POOL || pick > pool {
+                return Err(Error::InvalidInput);
+            }
+            let mut balls = [0u8; MAX_LOTTERY_POOL];
+            for (i, ball) in balls.iter_mut().enumerate().take(pool) {
+                *ball = i as u8 + 1;
+            }
+            // 部分Fisher–Yates：前pick个位置即为抽中的号码
+            for i in 0..pick {
+                let j = i + rng.gen_below((pool - i) as u128) as usize;
+                balls.swap(i, j);
+            }
+            output.copy_from_slice(&balls[..pick]);
+            output.sort_unstable();
+        }
+    }
+    Ok(())
+}
+
+/// 判定福彩3D彩票的中奖等级。
+///
+/// * 直选：三位号码按位相同时中直选奖
+/// * 组选：号码相同但顺序不限；开奖号码有两位相同时为组选三，三位各不相同时为组选六。
+///   开奖号码三位全同（豹子）时组选不中奖
+///
+/// # 参数
+///
+/// * `ticket` - 投注号码（3位，每位0–9）
+/// * `draw` - 开奖号码（3位，每位0–9）
+/// * `play` - 投注方式
+///
+/// # 返回值
+///
+/// * `Result<Prize3d>` - 成功时返回中奖等级
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{match_3d_ticket, Play3d, Prize3d};
+///
+/// let draw = [1u8, 2, 3];
+/// assert_eq!(match_3d_ticket(&[1, 2, 3], &draw, Play3d::Straight).unwrap(), Prize3d::Straight);
+/// assert_eq!(match_3d_ticket(&[3, 2, 1], &draw, Play3d::Box).unwrap(), Prize3d::Group6);
+/// assert_eq!(match_3d_ticket(&[3, 2, 1], &draw, Play3d::Straight).unwrap(), Prize3d::NoPrize);
+/// ```
+pub fn match_3d_ticket(ticket: &[u8], draw: &[u8], play: Play3d) -> Result<Prize3d> {
+    if ticket.len() != 3 || draw.len() != 3 {
+        return Err(Error::InvalidInput);
+    }
+    if ticket.iter().chain(draw.iter()).any(|&d| d > 9) {
+        return Err(Error::InvalidInput);
+    }
+
+    match play {
+        Play3d::Straight => Ok(if ticket == draw {
+            Prize3d::Straight
+        } else {
+            Prize3d::NoPrize
+        }),
+        Play3d::Box => {
+            let mut t = [ticket[0], ticket[1], ticket[2]];
+            let mut d = [draw[0], draw[1], draw[2]];
+            t.sort_unstable();
+            d.sort_unstable();
+            if t != d {
+                return Ok(Prize3d::NoPrize);
+            }
+            Ok(match distinct_digits(&d) {
+                3 => Prize3d::Group6,
+                2 => Prize3d::Group3,
+                _ => Prize3d::NoPrize,
+            })
+        }
+    }
+}
+
+/// 已排序的三位号码中不同数字的个数
+fn distinct_digits(sorted: &[u8; 3]) -> usize {
+    1 + (sorted[0] != sorted[1]) as usize + (sorted[1] != sorted[2]) as usize
+}
+
+/// 统计彩票号码与开奖号码的匹配个数。
+///
+/// * 数字型：按位比较，统计位置和数字都相同的位数
+/// * 乐透型：统计两组号码中共同出现的号码个数（与顺序无关）；两组号码都必须在1..=pool内
+///   且互不相同，否则一个开奖号码可能被重复计数
+///
+/// # 参数
+///
+/// * `ticket` - 投注号码
+/// * `draw` - 开奖号码
+/// * `format` - 彩票玩法格式
+///
+/// # 返回值
+///
+/// * `Result<usize>` - 成功时返回匹配个数；乐透型号码重复或超出号码池时返回InvalidInput
+pub fn count_lottery_matches(ticket: &[u8], draw: &[u8], format: LotteryFormat) -> Result<usize> {
+    let len = format.draw_len();
+    if ticket.len() != len || draw.len() != len {
+        return Err(Error::InvalidInput);
+    }
+    match format {
+        LotteryFormat::Digits(_) => Ok(ticket.iter().zip(draw).filter(|(t, d)| t == d).count()),
+        LotteryFormat::Pick { pool, .. } => {
+            if !distinct_in_pool(ticket, pool) || !distinct_in_pool(draw, pool) {
+                return Err(Error::InvalidInput);
+            }
+            Ok(ticket.iter().filter(|t| draw.contains(t)).count())
+        }
+    }
+}
+
+/// 乐透型号码是否都在1..=pool内且互不相同
+fn distinct_in_pool(numbers: &[u8], pool: usize) -> bool {
+    let mut seen = [false; MAX_LOTTERY_POOL + 1];
+    for &x in numbers {
+        let x = x as usize;
+        if x == 0 || x > pool || seen[x] {
+            return false;
+        }
+        seen[x] = true;
+    }
+    true
+}
@@ -0,0 +1,205 @@
+//! 共识安全的整数运算辅助函数。
+//!
+//! 所有函数都只使用整数运算，保证在任何平台上结果完全一致。
+
+const LOW_MASK: u128 = u64::MAX as u128;
+
+/// 计算u128乘积的完整256位结果，返回 (高128位, 低128位)。
+#[inline]
+pub(crate) fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+    let (a1, a0) = (a >> 64, a & LOW_MASK);
+    let (b1, b0) = (b >> 64, b & LOW_MASK);
+
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+
+    let mid = (p00 >> 64) + (p01 & LOW_MASK) + (p10 & LOW_MASK);
+    let lo = (p00 & LOW_MASK) | (mid << 64);
+    let hi = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+    (hi, lo)
+}
+
+/// 256位被除数除以128位除数，返回 (商, 余数)。商溢出u128时返回None。
+fn div_wide(hi: u128, lo: u128, d: u128) -> Option<(u128, u128)> {
+    if d == 0 || hi >= d {
+        return None;
+    }
+    if hi == 0 {
+        return Some((lo / d, lo % d));
+    }
+
+    // 逐位长除法
+    let mut rem = hi;
+    let mut q = 0u128;
+    for i in (0..128).rev() {
+        let carry = rem >> 127;
+        rem = (rem << 1) | ((lo >> i) & 1);
+        q <<= 1;
+        if carry == 1 || rem >= d {
+            rem = rem.wrapping_sub(d);
+            q |= 1;
+        }
+    }
+    Some((q, rem))
+}
+
+/// 计算 floor(a * b / c)，中间结果不会溢出。
+#[inline]
+pub(crate) fn mul_div(a: u128, b: u128, c: u128) -> Option<u128> {
+    let (hi, lo) = mul_wide(a, b);
+    div_wide(hi, lo, c).map(|(q, _)| q)
+}
+
+/// 计算 ceil(a * b / c)，中间结果不会溢出。
+#[inline]
+pub(crate) fn mul_div_ceil(a: u128, b: u128, c: u128) -> Option<u128> {
+    let (hi, lo) = mul_wide(a, b);
+    let (q, r) = div_wide(hi, lo, c)?;
+    if r == 0 {
+        Some(q)
+    } else {
+        q.checked_add(1)
+    }
+}
+
+/// 计算 (floor(a * b / c), a * b mod c)，中间结果不会溢出。
+#[inline]
+pub(crate) fn mul_div_rem(a: u128, b: u128, c: u128) -> Option<(u128, u128)> {
+    let (hi, lo) = mul_wide(a, b);
+    div_wide(hi, lo, c)
+}
+
+/// 计算 a * b mod m（要求 a, b < m），中间结果不会溢出。
+#[inline]
+pub(crate) fn mul_mod(a: u128, b: u128, m: u128) -> u128 {
+    let (hi, lo) = mul_wide(a, b);
+    // a, b < m 保证商不会溢出
+    div_wide(hi, lo, m).map_or(0, |(_, r)| r)
+}
+
+/// 计算 base^exp mod m（平方-乘算法）。
+pub(crate) fn pow_mod(base: u128, mut exp: u128, m: u128) -> u128 {
+    let mut result = 1 % m;
+    let mut b = base % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, b, m);
+        }
+        b = mul_mod(b, b, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// 最大公约数
+pub(crate) fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// 整数平方根：floor(sqrt(x))（牛顿迭代）。
+pub(crate) fn isqrt(x: u128) -> u128 {
+    if x < 2 {
+        return x;
+    }
+    // 初值取 2^ceil(bits/2)，保证不小于真实平方根
+    let mut r = 1u128 << ((128 - x.leading_zeros() + 1) / 2);
+    loop {
+        let next = (r + x / r) / 2;
+        if next >= r {
+            return r;
+        }
+        r = next;
+    }
+}
+
+/// 以64位分段表示的 a * b * c（小端序）
+fn mul3_limbs(a: u128, b: u128, c: u64) -> [u64; 5] {
+    let (hi, lo) = mul_wide(a, b);
+    let src = [lo as u64, (lo >> 64) as u64, hi as u64, (hi >> 64) as u64];
+    let mut out = [0u64; 5];
+    let mut carry = 0u128;
+    for (i, &limb) in src.iter().enumerate() {
+        let t = limb as u128 * c as u128 + carry;
+        out[i] = t as u64;
+        carry = t >> 64;
+    }
+    out[4] = carry as u64;
+    out
+}
+
+/// 精确比较 a * b * c 与 d * e * f 的大小，中间结果不会溢出。
+pub(crate) fn cmp_mul3(a: u128, b: u128, c: u64, d: u128, e: u128, f: u64) -> core::cmp::Ordering {
+    let left = mul3_limbs(a, b, c);
+    let right = mul3_limbs(d, e, f);
+    left.iter().rev().cmp(right.iter().rev())
+}
+
+#[inline]
+fn splitmix64(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// 计数器模式混合函数：由种子和计数器确定性地派生一个u128值。
+///
+/// 用于平局裁决等需要从去中心化随机数派生多个值的场合。
+/// 该函数不是密码学哈希，安全性来自种子本身的不可预测性。
+pub(crate) fn mix_u128(seed: u128, counter: u128) -> u128 {
+    let a = splitmix64(seed as u64 ^ splitmix64(counter as u64));
+    let b = splitmix64(
+        (seed >> 64) as u64 ^ a ^ splitmix64((counter >> 64) as u64 ^ 0x243F_6A88_85A3_08D3),
+    );
+    let lo = splitmix64(a ^ b.rotate_left(32));
+    ((b as u128) << 64) | lo as u128
+}
+
+/// 18位小数定点数的1.0
+pub(crate) const WAD: u128 = 1_000_000_000_000_000_000;
+
+/// ln(2)，18位小数定点数
+const LN_2_WAD: u128 = 693_147_180_559_945_309;
+
+/// 计算定点数x（18位小数，x > 0）的以2为底的对数，结果为18位小数定点数。
+fn log2_wad(x: u128) -> i128 {
+    if x < WAD {
+        // log2(x) = -log2(1/x)
+        return -log2_wad(WAD * WAD / x);
+    }
+
+    // 整数部分：x / WAD 的最高有效位
+    let n = 127 - (x / WAD).leading_zeros();
+    let mut result = n as i128 * WAD as i128;
+    let mut y = x >> n;
+
+    // 小数部分：反复平方，逐位确定二进制小数
+    let mut delta = WAD / 2;
+    while delta > 0 {
+        y = y * y / WAD;
+        if y >= 2 * WAD {
+            result += delta as i128;
+            y >>= 1;
+        }
+        delta >>= 1;
+    }
+    result
+}
+
+/// 计算定点数x（18位小数，x > 0）的自然对数，结果为18位小数定点数。
+pub(crate) fn ln_wad(x: u128) -> i128 {
+    let l = log2_wad(x);
+    let magnitude = mul_div(l.unsigned_abs(), LN_2_WAD, WAD).unwrap_or(u128::MAX) as i128;
+    if l < 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
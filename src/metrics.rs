@@ -0,0 +1,180 @@
+//! 去中心化程度指标。
+//!
+//! 针对验证者集合或代币持有者的`VotingPower`分布计算Nakamoto系数、
+//! Gini系数、Herfindahl–Hirschman指数和Theil指数。所有结果都使用
+//! 确定性的定点整数运算，可以直接在合约中使用。
+
+use core::cmp::Ordering;
+
+use crate::math::{cmp_mul3, ln_wad, mul_div, WAD};
+use crate::types::VotingPower;
+use crate::{Error, Result};
+
+/// 指标定点数精度：1.0 = `METRICS_SCALE`
+pub const METRICS_SCALE: u128 = 1_000_000_000;
+
+fn total_power(powers: &[VotingPower]) -> Result<u128> {
+    if powers.is_empty() {
+        return Err(Error::InvalidInput);
+    }
+    let mut total = 0u128;
+    for &p in powers {
+        total = total.checked_add(p).ok_or(Error::CalculationFailed)?;
+    }
+    if total == 0 {
+        return Err(Error::InvalidInput);
+    }
+    Ok(total)
+}
+
+/// 将分布复制到临时缓冲区并升序排序
+fn sorted<'a>(powers: &[VotingPower], scratch: &'a mut [VotingPower]) -> Result<&'a [VotingPower]> {
+    if scratch.len() < powers.len() {
+        return Err(Error::InvalidInput);
+    }
+    let buf = &mut scratch[..powers.len()];
+    buf.copy_from_slice(powers);
+    buf.sort_unstable();
+    Ok(buf)
+}
+
+/// 每个实体的份额（18位小数定点数）
+#[inline]
+fn share_wad(power: VotingPower, total: u128) -> Result<u128> {
+    mul_div(power, WAD, total).ok_or(Error::CalculationFailed)
+}
+
+/// 计算Nakamoto系数：合计权力超过给定控制阈值所需的最少实体数。
+///
+/// 阈值以分数 `threshold_num / threshold_den` 表示，例如1/3（可阻止BFT共识）
+/// 或1/2（可控制多数决）。当前k大实体的权力之和严格大于 总权力 × 阈值 时，
+/// 系数为k。
+///
+/// # 参数
+///
+/// * `powers` - 每个实体的投票权重
+/// * `threshold_num` - 阈值分子
+/// * `threshold_den` - 阈值分母（必须 > threshold_num）
+/// * `scratch` - 用于排序的临时缓冲区（长度必须 >= powers.len()）
+///
+/// # 返回值
+///
+/// * `Result<usize>` - 成功时返回Nakamoto系数
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::calculate_nakamoto_coefficient;
+///
+/// let powers = [40u128, 25, 15, 10, 10];
+/// let mut scratch = [0u128; 5];
+/// assert_eq!(calculate_nakamoto_coefficient(&powers, 1, 3, &mut scratch).unwrap(), 1);
+/// assert_eq!(calculate_nakamoto_coefficient(&powers, 1, 2, &mut scratch).unwrap(), 2);
+/// ```
+pub fn calculate_nakamoto_coefficient(
+    powers: &[VotingPower],
+    threshold_num: u128,
+    threshold_den: u128,
+    scratch: &mut [VotingPower],
+) -> Result<usize> {
+    if threshold_den == 0 || threshold_num >= threshold_den {
+        return Err(Error::InvalidInput);
+    }
+    let total = total_power(powers)?;
+    let asc = sorted(powers, scratch)?;
+
+    let mut acc = 0u128;
+    for (count, &p) in asc.iter().rev().enumerate() {
+        acc += p;
+        // acc / total > num / den
+        if cmp_mul3(acc, threshold_den, 1, total, threshold_num, 1) == Ordering::Greater {
+            return Ok(count + 1);
+        }
+    }
+    Err(Error::CalculationFailed)
+}
+
+/// 计算Gini系数（0表示完全平均，接近1表示高度集中）。
+///
+/// G = Σ (2i - n - 1) · s_i / n，其中s_i为按升序排列后第i个实体（从1开始）的份额。
+///
+/// # 参数
+///
+/// * `powers` - 每个实体的投票权重
+/// * `scratch` - 用于排序的临时缓冲区（长度必须 >= powers.len()）
+///
+/// # 返回值
+///
+/// * `Result<u128>` - 成功时返回Gini系数（定点数，精度为`METRICS_SCALE`）
+pub fn calculate_gini_coefficient(
+    powers: &[VotingPower],
+    scratch: &mut [VotingPower],
+) -> Result<u128> {
+    let total = total_power(powers)?;
+    let asc = sorted(powers, scratch)?;
+    let n = asc.len() as i128;
+
+    let mut acc = 0i128;
+    for (i, &p) in asc.iter().enumerate() {
+        let rank = i as i128 + 1;
+        let s = share_wad(p, total)? as i128;
+        acc = (2 * rank - n - 1)
+            .checked_mul(s)
+            .and_then(|t| acc.checked_add(t))
+            .ok_or(Error::CalculationFailed)?;
+    }
+    let gini_wad = (acc.max(0) / n) as u128;
+    Ok(gini_wad / (WAD / METRICS_SCALE))
+}
+
+/// 计算Herfindahl–Hirschman指数：各实体份额的平方和。
+///
+/// 取值范围为 [1/n, 1]，1表示完全由单一实体控制。
+/// 乘以10,000即为反垄断审查中常用的HHI点数。
+///
+/// # 参数
+///
+/// * `powers` - 每个实体的投票权重
+///
+/// # 返回值
+///
+/// * `Result<u128>` - 成功时返回HHI（定点数，精度为`METRICS_SCALE`）
+pub fn calculate_hhi(powers: &[VotingPower]) -> Result<u128> {
+    let total = total_power(powers)?;
+    let mut acc = 0u128;
+    for &p in powers {
+        let s = share_wad(p, total)?;
+        acc += s * s / WAD;
+    }
+    Ok(acc / (WAD / METRICS_SCALE))
+}
+
+/// 计算Theil指数（广义熵指数GE(1)）。
+///
+/// T = Σ s_i · ln(n · s_i)，取值范围为 [0, ln n]，0表示完全平均。
+/// 份额为0的实体贡献为0。
+///
+/// # 参数
+///
+/// * `powers` - 每个实体的投票权重
+///
+/// # 返回值
+///
+/// * `Result<u128>` - 成功时返回Theil指数（定点数，精度为`METRICS_SCALE`）
+pub fn calculate_theil_index(powers: &[VotingPower]) -> Result<u128> {
+    let total = total_power(powers)?;
+    let n = powers.len() as u128;
+
+    let mut acc = 0i128;
+    for &p in powers {
+        let s = share_wad(p, total)?;
+        if s == 0 {
+            continue;
+        }
+        let ratio = s.checked_mul(n).ok_or(Error::CalculationFailed)?;
+        let ln = ln_wad(ratio);
+        let magnitude = mul_div(s, ln.unsigned_abs(), WAD).ok_or(Error::CalculationFailed)? as i128;
+        acc += if ln < 0 { -magnitude } else { magnitude };
+    }
+    Ok(acc.max(0) as u128 / (WAD / METRICS_SCALE))
+}
@@ -0,0 +1,309 @@
+//! 基于ristretto255的Pedersen可验证秘密共享。
+//!
+//! 与`shamir`模块的用途相同：贡献者在承诺阶段就把自己的值按t-of-n门限拆分给其他参与者，
+//! 若其拒绝揭示，任意t个诚实参与者即可恢复其值。区别在于承诺方式：
+//!
+//! * `shamir`的Feldman承诺 C_0 = g^secret 不隐藏秘密——127位群上的离散对数可以用数域筛法求解，
+//!   即使换成椭圆曲线群，128位的秘密也可以用袋鼠法在约2^64次群运算内求出
+//! * 本模块的Pedersen承诺 C_j = a_j·G + b_j·H 对秘密是无条件隐藏的：
+//!   任何人在揭示之前都无法从承诺中得到秘密的任何信息
+//!
+//! 承诺的绑定性依赖ristretto255上的离散对数（约126位安全性），H由固定的域分隔字符串
+//! 哈希到群上得到，没有人知道 log_G(H)。
+//!
+//! 需要启用`pedersen`特性。
+
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::Identity;
+use sha2::{Digest, Sha512};
+
+use crate::shamir::MAX_SHAMIR_SHARES;
+use crate::{Error, Result};
+
+/// 生成第二个生成元H使用的域分隔字符串
+const H_DOMAIN: &[u8] = b"dd_algorithms_lib pedersen generator H";
+
+/// 一个Pedersen份额：(f(index), r(index))
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PedersenShare {
+    /// 份额编号 x（从1开始，0保留给秘密本身）
+    pub index: u64,
+    /// 秘密多项式的值 f(x)（规范编码的标量，小端序）
+    pub value: [u8; 32],
+    /// 盲化多项式的值 r(x)（规范编码的标量，小端序）
+    pub blinding: [u8; 32],
+}
+
+/// 第二个生成元H
+fn generator_h() -> RistrettoPoint {
+    let mut bytes = [0u8; 64];
+    bytes.copy_from_slice(&Sha512::digest(H_DOMAIN));
+    RistrettoPoint::from_uniform_bytes(&bytes)
+}
+
+/// a·G + b·H
+fn commit(a: &Scalar, b: &Scalar, h: &RistrettoPoint) -> RistrettoPoint {
+    RistrettoPoint::vartime_double_scalar_mul_basepoint(b, h, a)
+}
+
+/// 解码规范编码的标量
+fn scalar_from_bytes(bytes: &[u8; 32]) -> Option<Scalar> {
+    Option::from(Scalar::from_canonical_bytes(*bytes))
+}
+
+/// 在 x 处计算以`coefficients`为系数（从常数项开始）的多项式（霍纳法）
+fn evaluate(coefficients: &[Scalar], x: &Scalar) -> Scalar {
+    coefficients
+        .iter()
+        .rev()
+        .fold(Scalar::ZERO, |acc, c| acc * x + c)
+}
+
+/// 将秘密拆分为n个份额，任意t个份额可以恢复秘密，并生成Pedersen承诺。
+///
+/// 秘密多项式 f(x) = secret + a_1·x + ... + a_{t-1}·x^{t-1}，盲化多项式
+/// r(x) = b_0 + b_1·x + ... + b_{t-1}·x^{t-1}（均在ristretto255的标量域上），
+/// 第i个份额为 (i, f(i), r(i))，承诺为 C_j = a_j·G + b_j·H（a_0 = secret）。
+///
+/// 系数和盲化值都取64字节的均匀随机数并按群阶归约，由贡献者从自己的私有随机源取得；
+/// 揭示时贡献者同时公开秘密和`blindings[0]`，其他人用`pedersen_verify_opening`检查。
+///
+/// # 参数
+///
+/// * `secret` - 要共享的秘密
+/// * `coefficients` - t-1个随机系数
+/// * `blindings` - t个随机盲化值
+/// * `shares` - 用于存储n个份额的可变切片（t <= n <= 256），编号依次为1..=n
+/// * `commitments` - 用于存储t个承诺（压缩的ristretto255点）的可变切片
+///
+/// # 返回值
+///
+/// * `Result<()>` - 成功时返回Ok，输入无效时返回InvalidInput
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{
+///     pedersen_reconstruct_verified, pedersen_split, pedersen_verify_opening,
+///     pedersen_verify_share, PedersenShare,
+/// };
+///
+/// // 参与者2承诺了值；随机数应来自贡献者自己的私有随机源
+/// let withheld = 0x1234_5678_9abc_def0u128;
+/// let coefficients = [[7u8; 64], [8u8; 64]];
+/// let blindings = [[1u8; 64], [2u8; 64], [3u8; 64]];
+/// let mut shares = [PedersenShare::default(); 5];
+/// let mut commitments = [[0u8; 32]; 3];
+/// pedersen_split(withheld, &coefficients, &blindings, &mut shares, &mut commitments).unwrap();
+/// assert!(shares.iter().all(|s| pedersen_verify_share(s, &commitments).unwrap()));
+///
+/// // 按时揭示：公开秘密和blindings[0]
+/// assert!(pedersen_verify_opening(withheld, &blindings[0], &commitments[0]).unwrap());
+///
+/// // 拒绝揭示：任意3个参与者交出份额即可恢复
+/// let recovered = pedersen_reconstruct_verified(&shares[2..], &commitments).unwrap();
+/// assert_eq!(recovered, withheld);
+/// ```
+pub fn pedersen_split(
+    secret: u128,
+    coefficients: &[[u8; 64]],
+    blindings: &[[u8; 64]],
+    shares: &mut [PedersenShare],
+    commitments: &mut [[u8; 32]],
+) -> Result<()> {
+    // 验证输入参数
+    let t = coefficients.len() + 1;
+    let n = shares.len();
+    if n < t || n > MAX_SHAMIR_SHARES || blindings.len() != t || commitments.len() != t {
+        return Err(Error::InvalidInput);
+    }
+
+    let mut f = [Scalar::ZERO; MAX_SHAMIR_SHARES];
+    let mut r = [Scalar::ZERO; MAX_SHAMIR_SHARES];
+    f[0] = Scalar::from(secret);
+    for (a, bytes) in f[1..t].iter_mut().zip(coefficients) {
+        *a = Scalar::from_bytes_mod_order_wide(bytes);
+    }
+    for (b, bytes) in r[..t].iter_mut().zip(blindings) {
+        *b = Scalar::from_bytes_mod_order_wide(bytes);
+    }
+
+    for (i, share) in shares.iter_mut().enumerate() {
+        let x = Scalar::from(i as u64 + 1);
+        *share = PedersenShare {
+            index: i as u64 + 1,
+            value: evaluate(&f[..t], &x).to_bytes(),
+            blinding: evaluate(&r[..t], &x).to_bytes(),
+        };
+    }
+
+    let h = generator_h();
+    for (c, (a, b)) in commitments.iter_mut().zip(f.iter().zip(&r)) {
+        *c = commit(a, b, &h).compress().to_bytes();
+    }
+    Ok(())
+}
+
+/// 解码承诺，任何一个不是有效的ristretto255点时返回InvalidInput
+fn decode_commitments(
+    commitments: &[[u8; 32]],
+    points: &mut [RistrettoPoint; MAX_SHAMIR_SHARES],
+) -> Result<usize> {
+    if commitments.is_empty() || commitments.len() > MAX_SHAMIR_SHARES {
+        return Err(Error::InvalidInput);
+    }
+    for (p, c) in points.iter_mut().zip(commitments) {
+        *p = CompressedRistretto(*c)
+            .decompress()
+            .ok_or(Error::InvalidInput)?;
+    }
+    Ok(commitments.len())
+}
+
+/// 用已解码的承诺验证份额
+fn verify_share_points(
+    share: &PedersenShare,
+    points: &[RistrettoPoint],
+    h: &RistrettoPoint,
+) -> bool {
+    let (Some(value), Some(blinding)) = (
+        scalar_from_bytes(&share.value),
+        scalar_from_bytes(&share.blinding),
+    ) else {
+        return false;
+    };
+    if share.index == 0 {
+        return false;
+    }
+
+    // 点上的霍纳法：((C_{t-1})·i + C_{t-2})·i + ... + C_0
+    let x = Scalar::from(share.index);
+    let expected = points
+        .iter()
+        .rev()
+        .fold(RistrettoPoint::identity(), |acc, c| acc * x + c);
+    commit(&value, &blinding, h) == expected
+}
+
+/// 使用Pedersen承诺验证一个份额：f(i)·G + r(i)·H = Σ C_j·i^j。
+///
+/// # 参数
+///
+/// * `share` - 要验证的份额
+/// * `commitments` - 贡献者公布的t个承诺
+///
+/// # 返回值
+///
+/// * `Result<bool>` - 份额与承诺一致时返回true；承诺不是有效的ristretto255点时返回InvalidInput
+pub fn pedersen_verify_share(share: &PedersenShare, commitments: &[[u8; 32]]) -> Result<bool> {
+    let mut points = [RistrettoPoint::identity(); MAX_SHAMIR_SHARES];
+    let t = decode_commitments(commitments, &mut points)?;
+    Ok(verify_share_points(share, &points[..t], &generator_h()))
+}
+
+/// 检查揭示的秘密与承诺C_0是否一致：secret·G + b_0·H = C_0。
+///
+/// # 参数
+///
+/// * `secret` - 揭示的秘密
+/// * `blinding` - 揭示的盲化值（`pedersen_split`的`blindings[0]`）
+/// * `commitment` - 贡献者公布的承诺C_0
+///
+/// # 返回值
+///
+/// * `Result<bool>` - 一致时返回true；承诺不是有效的ristretto255点时返回InvalidInput
+pub fn pedersen_verify_opening(
+    secret: u128,
+    blinding: &[u8; 64],
+    commitment: &[u8; 32],
+) -> Result<bool> {
+    let c0 = CompressedRistretto(*commitment)
+        .decompress()
+        .ok_or(Error::InvalidInput)?;
+    let b0 = Scalar::from_bytes_mod_order_wide(blinding);
+    Ok(commit(&Scalar::from(secret), &b0, &generator_h()) == c0)
+}
+
+/// 验证份额后恢复秘密，并用承诺C_0检查结果。
+///
+/// 跳过未通过验证或编号重复的份额，取前t个有效份额同时插值 f(0) 和 r(0)
+/// （t = commitments.len()），最后检查 f(0)·G + r(0)·H = C_0。
+///
+/// # 参数
+///
+/// * `shares` - 收集到的份额（数量 <= 256）
+/// * `commitments` - 贡献者公布的t个承诺
+///
+/// # 返回值
+///
+/// * `Result<u128>` - 成功时返回恢复的秘密；有效份额少于t时返回NotEnoughParticipants，
+///   恢复结果与C_0不一致或超出u128范围时返回CalculationFailed
+pub fn pedersen_reconstruct_verified(
+    shares: &[PedersenShare],
+    commitments: &[[u8; 32]],
+) -> Result<u128> {
+    if shares.len() > MAX_SHAMIR_SHARES {
+        return Err(Error::InvalidInput);
+    }
+    let mut points = [RistrettoPoint::identity(); MAX_SHAMIR_SHARES];
+    let t = decode_commitments(commitments, &mut points)?;
+    let h = generator_h();
+
+    let mut used = [false; MAX_SHAMIR_SHARES];
+    let mut valid = 0usize;
+    for (i, share) in shares.iter().enumerate() {
+        if valid == t {
+            break;
+        }
+        // 跳过编号重复的份额
+        if shares[..i]
+            .iter()
+            .zip(&used[..i])
+            .any(|(s, &u)| u && s.index == share.index)
+        {
+            continue;
+        }
+        if verify_share_points(share, &points[..t], &h) {
+            used[i] = true;
+            valid += 1;
+        }
+    }
+    if valid < t {
+        return Err(Error::NotEnoughParticipants);
+    }
+
+    // 拉格朗日插值，已验证的份额都是规范编码
+    let mut secret = Scalar::ZERO;
+    let mut blinding = Scalar::ZERO;
+    for (i, si) in shares.iter().enumerate() {
+        if !used[i] {
+            continue;
+        }
+        let xi = Scalar::from(si.index);
+        let mut num = Scalar::ONE;
+        let mut den = Scalar::ONE;
+        for (j, sj) in shares.iter().enumerate() {
+            if j == i || !used[j] {
+                continue;
+            }
+            let xj = Scalar::from(sj.index);
+            num *= xj;
+            den *= xj - xi;
+        }
+        let basis = num * den.invert();
+        secret += scalar_from_bytes(&si.value).ok_or(Error::CalculationFailed)? * basis;
+        blinding += scalar_from_bytes(&si.blinding).ok_or(Error::CalculationFailed)? * basis;
+    }
+    if commit(&secret, &blinding, &h) != points[0] {
+        return Err(Error::CalculationFailed);
+    }
+
+    let bytes = secret.to_bytes();
+    if bytes[16..].iter().any(|&b| b != 0) {
+        return Err(Error::CalculationFailed);
+    }
+    let mut low = [0u8; 16];
+    low.copy_from_slice(&bytes[..16]);
+    Ok(u128::from_le_bytes(low))
+}
@@ -0,0 +1,270 @@
+//! 加权投票博弈的投票权力指数。
+//!
+//! 在加权多数博弈中，成员的`VotingPower`并不等于其真实影响力。本模块计算
+//! 标准化Banzhaf指数和Shapley–Shubik指数：小规模博弈使用伪多项式动态规划
+//! 精确计算，大规模博弈使用由去中心化随机数驱动的蒙特卡洛估计。
+
+use crate::math::{mix_u128, mul_div};
+use crate::types::VotingPower;
+use crate::{Error, Result};
+
+/// 最大成员数
+pub const MAX_POWER_INDEX_PLAYERS: usize = 64;
+
+/// 指数定点数精度：所有成员的指数之和约为 `POWER_INDEX_SCALE`
+pub const POWER_INDEX_SCALE: u128 = 1_000_000_000;
+
+/// 投票权力指数类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerIndex {
+    /// 标准化Banzhaf指数：关键加入次数占所有关键加入次数的比例
+    Banzhaf,
+    /// Shapley–Shubik指数：在随机排列中成为关键成员的概率
+    ShapleyShubik,
+}
+
+/// 精确计算所需的临时缓冲区长度：2 * (n + 1) * quota
+pub fn power_index_scratch_len(n: usize, quota: VotingPower) -> usize {
+    usize::try_from(quota)
+        .unwrap_or(usize::MAX)
+        .saturating_mul(n + 1)
+        .saturating_mul(2)
+}
+
+fn validate(weights: &[VotingPower], quota: VotingPower, output: &[u128]) -> Result<()> {
+    if weights.is_empty()
+        || weights.len() > MAX_POWER_INDEX_PLAYERS
+        || output.len() != weights.len()
+    {
+        return Err(Error::InvalidInput);
+    }
+    let mut total = 0u128;
+    for &w in weights {
+        total = total.checked_add(w).ok_or(Error::CalculationFailed)?;
+    }
+    // 配额必须为正，且全体成员的联盟必须获胜
+    if quota == 0 || quota > total {
+        return Err(Error::InvalidInput);
+    }
+    Ok(())
+}
+
+/// 通过伪多项式动态规划精确计算加权多数博弈的投票权力指数。
+///
+/// 权重之和达到`quota`的联盟获胜。动态规划只统计权重和小于`quota`的联盟，
+/// 因此时间复杂度为 O(n² · quota)，适用于配额较小（或权重已按比例缩小）的博弈。
+///
+/// # 参数
+///
+/// * `weights` - 每个成员的投票权重（成员数 <= 64）
+/// * `quota` - 获胜所需的最低权重（0 < quota <= 权重之和）
+/// * `index` - 要计算的指数类型
+/// * `scratch` - 临时缓冲区（长度必须 >= `power_index_scratch_len(n, quota)`）
+/// * `output` - 用于存储每个成员指数的可变切片（定点数，精度为`POWER_INDEX_SCALE`）
+///
+/// # 返回值
+///
+/// * `Result<()>` - 成功时返回Ok，缓冲区不足时返回InvalidInput（可改用`estimate_power_index`）
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{calculate_power_index, power_index_scratch_len, PowerIndex};
+///
+/// // 权重 [50, 49, 1]，配额51：成员0的Shapley–Shubik指数为2/3，其余两人各为1/6
+/// let weights = [50u128, 49, 1];
+/// let mut scratch = [0u128; 512];
+/// assert!(power_index_scratch_len(3, 51) <= scratch.len());
+/// let mut output = [0u128; 3];
+/// calculate_power_index(&weights, 51, PowerIndex::ShapleyShubik, &mut scratch, &mut output).unwrap();
+/// assert_eq!(output[0], 666_666_666);
+/// assert_eq!(output[1], 166_666_666);
+/// ```
+pub fn calculate_power_index(
+    weights: &[VotingPower],
+    quota: VotingPower,
+    index: PowerIndex,
+    scratch: &mut [u128],
+    output: &mut [u128],
+) -> Result<()> {
+    validate(weights, quota, output)?;
+    let n = weights.len();
+    let required = power_index_scratch_len(n, quota);
+    if required == usize::MAX || scratch.len() < required {
+        return Err(Error::InvalidInput);
+    }
+    let q = quota as usize;
+    let rows = n + 1;
+
+    // dp[k][s]：大小为k、权重和为s（s < quota）的联盟个数
+    let (dp, removed) = scratch[..required].split_at_mut(rows * q);
+    dp.iter_mut().for_each(|c| *c = 0);
+    dp[0] = 1;
+    for (count, &w) in weights.iter().enumerate() {
+        // 权重不小于配额的成员不会出现在任何 s < quota 的联盟中
+        if w >= quota {
+            continue;
+        }
+        let w = w as usize;
+        for k in (1..=count + 1).rev() {
+            for s in (w..q).rev() {
+                dp[k * q + s] += dp[(k - 1) * q + s - w];
+            }
+        }
+    }
+
+    // 逐个成员从dp中移除其贡献，统计其关键加入的联盟
+    let mut swings = [0u128; MAX_POWER_INDEX_PLAYERS];
+    let mut total_swings = 0u128;
+    for (i, &w) in weights.iter().enumerate() {
+        let low = quota.saturating_sub(w) as usize;
+        for k in 0..rows {
+            for s in 0..q {
+                let mut c = dp[k * q + s];
+                if k >= 1 && w < quota && (w as usize) <= s {
+                    c -= removed[(k - 1) * q + s - w as usize];
+                }
+                removed[k * q + s] = c;
+            }
+        }
+
+        match index {
+            PowerIndex::Banzhaf => {
+                let mut count = 0u128;
+                for k in 0..n {
+                    for s in low..q {
+                        count += removed[k * q + s];
+                    }
+                }
+                swings[i] = count;
+                total_swings += count;
+            }
+            PowerIndex::ShapleyShubik => {
+                // 大小为k的关键联盟权重为 k!(n-1-k)!/n! = 1 / (n · C(n-1, k))
+                let mut value = 0u128;
+                let mut binom = 1u128;
+                for k in 0..n {
+                    let mut count = 0u128;
+                    for s in low..q {
+                        count += removed[k * q + s];
+                    }
+                    let den = binom * n as u128;
+                    value +=
+                        mul_div(count, POWER_INDEX_SCALE, den).ok_or(Error::CalculationFailed)?;
+                    binom = binom * (n - 1 - k) as u128 / (k as u128 + 1);
+                }
+                output[i] = value;
+            }
+        }
+    }
+
+    if index == PowerIndex::Banzhaf {
+        normalize(&swings[..n], total_swings, output)?;
+    }
+    Ok(())
+}
+
+fn normalize(swings: &[u128], total: u128, output: &mut [u128]) -> Result<()> {
+    for (out, &s) in output.iter_mut().zip(swings) {
+        *out = if total == 0 {
+            0
+        } else {
+            mul_div(s, POWER_INDEX_SCALE, total).ok_or(Error::CalculationFailed)?
+        };
+    }
+    Ok(())
+}
+
+/// 使用蒙特卡洛抽样估计加权多数博弈的投票权力指数。
+///
+/// 当配额过大无法精确计算时使用。随机数流由`seed`（通常为
+/// `get_one_dd_rand_num`的输出）以计数器模式派生，相同的种子和样本数
+/// 在任何节点上都得到相同的估计值。
+///
+/// * Banzhaf：每个样本随机选择一个联盟（每个成员以1/2概率加入），统计每个成员是否关键
+/// * Shapley–Shubik：每个样本随机生成一个成员排列，统计使联盟首次获胜的关键成员
+///
+/// # 参数
+///
+/// * `weights` - 每个成员的投票权重（成员数 <= 64）
+/// * `quota` - 获胜所需的最低权重（0 < quota <= 权重之和）
+/// * `index` - 要估计的指数类型
+/// * `seed` - 去中心化随机种子
+/// * `samples` - 样本数（必须 > 0）
+/// * `output` - 用于存储每个成员指数估计值的可变切片（定点数，精度为`POWER_INDEX_SCALE`）
+///
+/// # 返回值
+///
+/// * `Result<()>` - 成功时返回Ok，计算失败时返回Error
+pub fn estimate_power_index(
+    weights: &[VotingPower],
+    quota: VotingPower,
+    index: PowerIndex,
+    seed: u128,
+    samples: usize,
+    output: &mut [u128],
+) -> Result<()> {
+    validate(weights, quota, output)?;
+    if samples == 0 {
+        return Err(Error::InvalidInput);
+    }
+    let n = weights.len();
+    let mut counter = 0u128;
+    let mut hits = [0u128; MAX_POWER_INDEX_PLAYERS];
+
+    match index {
+        PowerIndex::Banzhaf => {
+            let mut total_swings = 0u128;
+            for _ in 0..samples {
+                // 每个成员以1/2概率加入联盟（n <= 64，一个随机数足够）
+                let bits = mix_u128(seed, counter);
+                counter += 1;
+                let mut coalition = 0u128;
+                for (i, &w) in weights.iter().enumerate() {
+                    if (bits >> i) & 1 == 1 {
+                        coalition += w;
+                    }
+                }
+                for (i, &w) in weights.iter().enumerate() {
+                    let without = if (bits >> i) & 1 == 1 {
+                        coalition - w
+                    } else {
+                        coalition
+                    };
+                    if without < quota && without + w >= quota {
+                        hits[i] += 1;
+                        total_swings += 1;
+                    }
+                }
+            }
+            normalize(&hits[..n], total_swings, output)?;
+        }
+        PowerIndex::ShapleyShubik => {
+            let mut order = [0usize; MAX_POWER_INDEX_PLAYERS];
+            for _ in 0..samples {
+                // Fisher–Yates生成随机排列
+                for (i, slot) in order.iter_mut().enumerate().take(n) {
+                    *slot = i;
+                }
+                for i in (1..n).rev() {
+                    let j = (mix_u128(seed, counter) % (i as u128 + 1)) as usize;
+                    counter += 1;
+                    order.swap(i, j);
+                }
+                let mut acc = 0u128;
+                for &p in &order[..n] {
+                    acc += weights[p];
+                    if acc >= quota {
+                        hits[p] += 1;
+                        break;
+                    }
+                }
+            }
+            for (out, &h) in output.iter_mut().zip(&hits[..n]) {
+                *out = mul_div(h, POWER_INDEX_SCALE, samples as u128)
+                    .ok_or(Error::CalculationFailed)?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,220 @@
+//! 由去中心化随机种子扩展的确定性随机数流。
+//!
+//! `get_k_dd_rand_num`每抽取一个随机数都需要所有参与者各提交一个新值。
+//! `DdRng`只需一轮贡献：以`get_one_dd_rand_num`的输出作为ChaCha20密钥，
+//! 将其扩展为无限长的随机数流，任意多次抽取都能在所有节点上得到相同结果。
+//!
+//! 启用`rand_core`特性后，`DdRng`实现`rand_core::RngCore`和`rand_core::SeedableRng`，
+//! 可以直接用于`rand`生态中的分布和算法。
+
+use crate::algorithms::get_one_dd_rand_num;
+use crate::Result;
+
+/// ChaCha20常量 "expand 32-byte k"
+const CHACHA_CONSTANTS: [u32; 4] = [0x6170_7865, 0x3320_646e, 0x7962_2d32, 0x6b20_6574];
+
+/// 每个ChaCha20块输出的32位字数
+const BLOCK_WORDS: usize = 16;
+
+#[inline(always)]
+fn quarter_round(s: &mut [u32; BLOCK_WORDS], a: usize, b: usize, c: usize, d: usize) {
+    s[a] = s[a].wrapping_add(s[b]);
+    s[d] = (s[d] ^ s[a]).rotate_left(16);
+    s[c] = s[c].wrapping_add(s[d]);
+    s[b] = (s[b] ^ s[c]).rotate_left(12);
+    s[a] = s[a].wrapping_add(s[b]);
+    s[d] = (s[d] ^ s[a]).rotate_left(8);
+    s[c] = s[c].wrapping_add(s[d]);
+    s[b] = (s[b] ^ s[c]).rotate_left(7);
+}
+
+/// 计算一个ChaCha20块（RFC 8439，nonce为0，64位块计数器）
+fn chacha20_block(key: &[u32; 8], counter: u64, out: &mut [u32; BLOCK_WORDS]) {
+    let mut input = [0u32; BLOCK_WORDS];
+    input[..4].copy_from_slice(&CHACHA_CONSTANTS);
+    input[4..12].copy_from_slice(key);
+    input[12] = counter as u32;
+    input[13] = (counter >> 32) as u32;
+
+    let mut s = input;
+    for _ in 0..10 {
+        // 列轮
+        quarter_round(&mut s, 0, 4, 8, 12);
+        quarter_round(&mut s, 1, 5, 9, 13);
+        quarter_round(&mut s, 2, 6, 10, 14);
+        quarter_round(&mut s, 3, 7, 11, 15);
+        // 对角轮
+        quarter_round(&mut s, 0, 5, 10, 15);
+        quarter_round(&mut s, 1, 6, 11, 12);
+        quarter_round(&mut s, 2, 7, 8, 13);
+        quarter_round(&mut s, 3, 4, 9, 14);
+    }
+    for ((o, &x), &i) in out.iter_mut().zip(&s).zip(&input) {
+        *o = x.wrapping_add(i);
+    }
+}
+
+/// 由去中心化随机种子驱动的确定性随机数生成器（ChaCha20）。
+///
+/// 输出流与RFC 8439中ChaCha20（nonce为0、块计数器从0开始）的密钥流逐字节相同，
+/// 不依赖平台字节序。
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::DdRng;
+///
+/// let values = [100u128, 200, 300, 400];
+/// let mut rng = DdRng::from_contributions(&values, 4).unwrap();
+/// let a = rng.next_u64();
+/// let die = rng.gen_below(6);
+/// assert!(die < 6);
+///
+/// // 相同的贡献值得到相同的随机数流
+/// let mut replay = DdRng::from_contributions(&values, 4).unwrap();
+/// assert_eq!(replay.next_u64(), a);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DdRng {
+    key: [u32; 8],
+    counter: u64,
+    block: [u32; BLOCK_WORDS],
+    index: usize,
+}
+
+impl DdRng {
+    /// 以u128种子创建生成器。
+    ///
+    /// 密钥为种子的16字节小端表示，后接16个零字节。
+    pub fn new(seed: u128) -> Self {
+        let mut key = [0u8; 32];
+        key[..16].copy_from_slice(&seed.to_le_bytes());
+        Self::from_key(key)
+    }
+
+    /// 以32字节ChaCha20密钥创建生成器
+    pub fn from_key(key: [u8; 32]) -> Self {
+        let mut words = [0u32; 8];
+        for (w, chunk) in words.iter_mut().zip(key.chunks_exact(4)) {
+            *w = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        DdRng {
+            key: words,
+            counter: 0,
+            block: [0; BLOCK_WORDS],
+            // 首次取数时生成第一个块
+            index: BLOCK_WORDS,
+        }
+    }
+
+    /// 以 (本轮贡献值, 上一轮输出) 创建链式生成器。
+    ///
+    /// 密钥为`contribution`的16字节小端表示后接`prev`的16字节小端表示，
+    /// 因此本轮的随机数流同时依赖本轮贡献和上一轮的结果。
+    pub fn chained(contribution: u128, prev: u128) -> Self {
+        let mut key = [0u8; 32];
+        key[..16].copy_from_slice(&contribution.to_le_bytes());
+        key[16..].copy_from_slice(&prev.to_le_bytes());
+        Self::from_key(key)
+    }
+
+    /// 由参与者贡献值创建生成器，种子为`get_one_dd_rand_num`的输出。
+    ///
+    /// # 参数
+    ///
+    /// * `values` - 表示参与者输入数据的u128值切片
+    /// * `n` - 输入数组中的值数量（应与values.len()匹配，且必须是2的n次幂）
+    ///
+    /// # 返回值
+    ///
+    /// * `Result<DdRng>` - 成功时返回生成器，输入无效时返回Error
+    pub fn from_contributions(values: &[u128], n: usize) -> Result<Self> {
+        let mut seed = 0u128;
+        get_one_dd_rand_num(values, n, &mut seed)?;
+        Ok(Self::new(seed))
+    }
+
+    fn refill(&mut self) {
+        chacha20_block(&self.key, self.counter, &mut self.block);
+        self.counter = self.counter.wrapping_add(1);
+        self.index = 0;
+    }
+
+    /// 返回下一个32位随机数
+    pub fn next_u32(&mut self) -> u32 {
+        if self.index >= BLOCK_WORDS {
+            self.refill();
+        }
+        let v = self.block[self.index];
+        self.index += 1;
+        v
+    }
+
+    /// 返回下一个64位随机数（先取的32位为低位）
+    pub fn next_u64(&mut self) -> u64 {
+        let lo = self.next_u32() as u64;
+        let hi = self.next_u32() as u64;
+        (hi << 32) | lo
+    }
+
+    /// 返回下一个128位随机数（先取的64位为低位）
+    pub fn next_u128(&mut self) -> u128 {
+        let lo = self.next_u64() as u128;
+        let hi = self.next_u64() as u128;
+        (hi << 64) | lo
+    }
+
+    /// 用随机字节填充`dest`（按密钥流顺序，每个32位字按小端展开）
+    pub fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(4) {
+            let bytes = self.next_u32().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    /// 在 [0, bound) 内均匀抽取一个整数（拒绝采样，无取模偏差）。
+    ///
+    /// `bound`为0时返回0。
+    pub fn gen_below(&mut self, bound: u128) -> u128 {
+        if bound <= 1 {
+            return 0;
+        }
+        // 拒绝落在最后一个不完整区间内的值
+        let zone = u128::MAX - (u128::MAX - bound + 1) % bound;
+        loop {
+            let v = self.next_u128();
+            if v <= zone {
+                return v % bound;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl rand_core::RngCore for DdRng {
+    fn next_u32(&mut self) -> u32 {
+        DdRng::next_u32(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        DdRng::next_u64(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        DdRng::fill_bytes(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> core::result::Result<(), rand_core::Error> {
+        DdRng::fill_bytes(self, dest);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl rand_core::SeedableRng for DdRng {
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        DdRng::from_key(seed)
+    }
+}
@@ -0,0 +1,159 @@
+//! 跨纪元的委员会轮换。
+//!
+//! 验证者或陪审团按纪元（epoch）轮换时，应避免同一成员在相邻纪元中连续当选。
+//! 本模块根据前几个纪元的选择结果自动确定冷却期内的成员，按冷却策略将其排除
+//! （与`get_k_dd_rand_num_with_whitelist`的排除语义相同）或降低其权重，调用者无需
+//! 手动构造排除列表。每个纪元的种子由本纪元的贡献值和上一纪元的种子共同派生，
+//! 形成一条可以逐纪元重放验证的链。
+
+use crate::algorithms::get_one_dd_rand_num;
+use crate::rng::DdRng;
+use crate::selection::draw_distinct;
+use crate::{Error, Result};
+
+/// 冷却期成员的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CooldownPolicy {
+    /// 冷却期成员不能被选中
+    Exclude,
+    /// 冷却期成员仍可被选中，但相对权重降为万分之`weight_bps`（其他成员为10,000）
+    DownWeight {
+        /// 冷却期成员的相对权重（万分之一，必须 <= 10,000）
+        weight_bps: u32,
+    },
+}
+
+/// 计算本纪元的种子：以 (贡献值异或结果, 上一纪元种子) 创建链式ChaCha20生成器，取密钥流的前128位
+fn epoch_rng(values: &[u128], n: usize, prev_seed: u128) -> Result<(DdRng, u128)> {
+    let mut contribution = 0u128;
+    get_one_dd_rand_num(values, n, &mut contribution)?;
+    let mut rng = DdRng::chained(contribution, prev_seed);
+    let seed = rng.next_u128();
+    Ok((rng, seed))
+}
+
+/// 选出本纪元的委员会，自动对最近`cooldown`个纪元的成员应用冷却策略。
+///
+/// # 算法
+///
+/// 1. 本纪元种子 = ChaCha20(密钥 = 贡献值异或结果 ‖ 上一纪元种子) 的前128位，
+///    随后的密钥流用于抽取
+/// 2. `history`中最后`cooldown`个纪元的成员进入冷却期
+/// 3. `Exclude`：在非冷却成员上执行部分Fisher–Yates洗牌，O(n + k)
+/// 4. `DownWeight`：按权重不放回地逐个抽取，O(n · k)
+///
+/// # 参数
+///
+/// * `values` - 本纪元参与者的u128贡献值切片
+/// * `n` - 参与者数量（应与values.len()匹配，且必须是2的n次幂）
+/// * `prev_seed` - 上一纪元返回的种子（首个纪元使用0）
+/// * `history` - 之前各纪元的选择结果，按时间顺序排列（最近的在最后）
+/// * `cooldown` - 冷却期长度（纪元数），0表示不限制
+/// * `policy` - 冷却期成员的处理方式
+/// * `scratch` - 临时缓冲区（长度必须 >= n）
+/// * `output` - 用于存储本纪元k个选中成员索引的可变切片
+///
+/// # 返回值
+///
+/// * `Result<u128>` - 成功时返回本纪元种子（作为下一纪元的`prev_seed`）；
+///   `Exclude`策略下非冷却成员少于k时返回NotEnoughParticipants
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{select_committee_epoch, CooldownPolicy};
+///
+/// let mut history: Vec<[usize; 2]> = Vec::new();
+/// let mut seed = 0u128;
+/// for epoch in 0..4u128 {
+///     let values = [epoch, 10 + epoch, 20 + epoch, 30 + epoch, 1, 2, 3, 4];
+///     let past: Vec<&[usize]> = history.iter().map(|c| c.as_slice()).collect();
+///     let mut scratch = [0usize; 8];
+///     let mut committee = [0usize; 2];
+///     seed = select_committee_epoch(
+///         &values, 8, seed, &past, 2, CooldownPolicy::Exclude, &mut scratch, &mut committee,
+///     )
+///     .unwrap();
+///     // 最近两个纪元的成员不会连任
+///     for prev in history.iter().rev().take(2) {
+///         assert!(committee.iter().all(|m| !prev.contains(m)));
+///     }
+///     history.push(committee);
+/// }
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn select_committee_epoch(
+    values: &[u128],
+    n: usize,
+    prev_seed: u128,
+    history: &[&[usize]],
+    cooldown: usize,
+    policy: CooldownPolicy,
+    scratch: &mut [usize],
+    output: &mut [usize],
+) -> Result<u128> {
+    // 验证输入参数
+    if output.is_empty() || output.len() > n || scratch.len() < n {
+        return Err(Error::InvalidInput);
+    }
+    if let CooldownPolicy::DownWeight { weight_bps } = policy {
+        if weight_bps > 10_000 {
+            return Err(Error::InvalidInput);
+        }
+    }
+    let (mut rng, seed) = epoch_rng(values, n, prev_seed)?;
+
+    // 标记冷却期成员
+    let recent = &history[history.len().saturating_sub(cooldown)..];
+    let marks = &mut scratch[..n];
+    marks.iter_mut().for_each(|m| *m = 0);
+    for epoch in recent {
+        for &idx in *epoch {
+            if idx >= n {
+                return Err(Error::InvalidInput);
+            }
+            marks[idx] = 1;
+        }
+    }
+
+    match policy {
+        CooldownPolicy::Exclude => {
+            // 原地压缩为非冷却成员的升序列表
+            let mut len = 0;
+            for i in 0..n {
+                if marks[i] == 0 {
+                    marks[len] = i;
+                    len += 1;
+                }
+            }
+            draw_distinct(&mut rng, &mut marks[..len], output)?;
+        }
+        CooldownPolicy::DownWeight { weight_bps } => {
+            // 将标记替换为权重；已选中的成员权重置0
+            let mut total = 0u128;
+            for m in marks.iter_mut() {
+                *m = if *m == 1 { weight_bps as usize } else { 10_000 };
+                total += *m as u128;
+            }
+            for out in output.iter_mut() {
+                if total == 0 {
+                    // 剩余成员都是权重为0的冷却期成员
+                    return Err(Error::NotEnoughParticipants);
+                }
+                let mut r = rng.gen_below(total);
+                let mut pick = 0;
+                for (i, &w) in marks.iter().enumerate() {
+                    if r < w as u128 {
+                        pick = i;
+                        break;
+                    }
+                    r -= w as u128;
+                }
+                total -= marks[pick] as u128;
+                marks[pick] = 0;
+                *out = pick;
+            }
+        }
+    }
+    Ok(seed)
+}
@@ -0,0 +1,250 @@
+//! 基于资格集合的去中心化随机选择。
+//!
+//! 所有n位参与者都贡献随机值，但只有满足资格条件的参与者可以被选中，
+//! 例如只从已完成KYC或已质押的成员中抽取委员会。资格条件用`Eligibility`
+//! 明确区分"只从给定集合中选择"（包含）和"排除给定集合"（排除）两种语义。
+//! 分层抽签按类别（地区、质押等级、角色等）分别分配席位，适用于公民大会式的委员会。
+
+use crate::rng::DdRng;
+use crate::{Error, Result};
+
+/// 分层抽签的最大类别数
+pub const MAX_STRATA: usize = 64;
+
+/// 资格条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Eligibility<'a> {
+    /// 所有参与者都有资格
+    All,
+    /// 包含语义：只有列出的索引有资格（列表中不能有重复）
+    Only(&'a [usize]),
+    /// 排除语义：除列出的索引外都有资格（与`get_k_dd_rand_num_with_whitelist`的`whitelist`相同）
+    Except(&'a [usize]),
+}
+
+/// `select_k_eligible`所需的临时缓冲区长度。
+///
+/// 包含语义只需要资格集合大小的缓冲区，因此当资格集合远小于n时开销很小。
+pub fn eligible_scratch_len(n: usize, eligibility: Eligibility) -> usize {
+    match eligibility {
+        Eligibility::Only(list) => list.len(),
+        Eligibility::All | Eligibility::Except(_) => n,
+    }
+}
+
+/// 将有资格的索引按升序写入`scratch`，返回资格集合大小
+pub(crate) fn collect_eligible(
+    n: usize,
+    eligibility: Eligibility,
+    scratch: &mut [usize],
+) -> Result<usize> {
+    if scratch.len() < eligible_scratch_len(n, eligibility) {
+        return Err(Error::InvalidInput);
+    }
+    match eligibility {
+        Eligibility::All => {
+            for (i, slot) in scratch[..n].iter_mut().enumerate() {
+                *slot = i;
+            }
+            Ok(n)
+        }
+        Eligibility::Only(list) => {
+            let buf = &mut scratch[..list.len()];
+            buf.copy_from_slice(list);
+            // 排序使结果与调用者给出的列表顺序无关
+            buf.sort_unstable();
+            if buf.last().is_some_and(|&last| last >= n) || buf.windows(2).any(|w| w[0] == w[1]) {
+                return Err(Error::InvalidInput);
+            }
+            Ok(list.len())
+        }
+        Eligibility::Except(list) => {
+            let buf = &mut scratch[..n];
+            for (i, slot) in buf.iter_mut().enumerate() {
+                *slot = i;
+            }
+            for &idx in list {
+                if idx >= n {
+                    return Err(Error::InvalidInput);
+                }
+                buf[idx] = usize::MAX;
+            }
+            // 原地压缩，保持升序
+            let mut len = 0;
+            for i in 0..n {
+                if buf[i] != usize::MAX {
+                    buf[len] = buf[i];
+                    len += 1;
+                }
+            }
+            Ok(len)
+        }
+    }
+}
+
+/// 从资格集合中不重复地选出k个参与者，每位有资格的参与者被选中的概率近似相等。
+///
+/// 结果由128位种子决定，有序选择的数目超过2^128时不能覆盖全部可能的结果。
+///
+/// 随机性来自全部n位参与者：以`get_one_dd_rand_num`的输出为种子创建`DdRng`，
+/// 在升序排列的资格集合上执行k步部分Fisher–Yates洗牌。资格集合只影响可被选中的
+/// 范围，不影响谁提供随机性。包含语义的时间复杂度为 O(m log m + k)（m为资格集合大小），
+/// 与n无关。
+///
+/// # 参数
+///
+/// * `values` - 表示参与者输入数据的u128值切片
+/// * `n` - 参与者数量（应与values.len()匹配，且必须是2的n次幂）
+/// * `eligibility` - 资格条件，索引必须在[0, n)范围内
+/// * `scratch` - 临时缓冲区（长度必须 >= `eligible_scratch_len(n, eligibility)`）
+/// * `output` - 用于存储k个选中参与者索引的可变切片
+///
+/// # 返回值
+///
+/// * `Result<()>` - 成功时返回Ok；有资格的参与者少于k时返回NotEnoughParticipants
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{select_k_eligible, Eligibility};
+///
+/// let values = [11u128, 22, 33, 44, 55, 66, 77, 88];
+/// // 只有已质押的参与者1、4、6有资格
+/// let staked = [6usize, 1, 4];
+/// let mut scratch = [0usize; 3];
+/// let mut output = [0usize; 2];
+/// select_k_eligible(&values, 8, Eligibility::Only(&staked), &mut scratch, &mut output).unwrap();
+/// assert!(output.iter().all(|i| staked.contains(i)));
+/// assert_ne!(output[0], output[1]);
+/// ```
+pub fn select_k_eligible(
+    values: &[u128],
+    n: usize,
+    eligibility: Eligibility,
+    scratch: &mut [usize],
+    output: &mut [usize],
+) -> Result<()> {
+    if output.is_empty() {
+        return Err(Error::InvalidInput);
+    }
+    let mut rng = DdRng::from_contributions(values, n)?;
+    let m = collect_eligible(n, eligibility, scratch)?;
+    draw_distinct(&mut rng, &mut scratch[..m], output)
+}
+
+/// 在`pool`上执行部分Fisher–Yates洗牌，取前k个元素写入`output`
+pub(crate) fn draw_distinct(
+    rng: &mut DdRng,
+    pool: &mut [usize],
+    output: &mut [usize],
+) -> Result<()> {
+    let k = output.len();
+    if k > pool.len() {
+        return Err(Error::NotEnoughParticipants);
+    }
+    for (i, out) in output.iter_mut().enumerate() {
+        let j = i + rng.gen_below((pool.len() - i) as u128) as usize;
+        pool.swap(i, j);
+        *out = pool[i];
+    }
+    Ok(())
+}
+
+/// 分层抽签：按类别配额从每个类别中不重复地随机选择参与者。
+///
+/// 以`get_one_dd_rand_num`的输出为种子创建`DdRng`，按类别编号从小到大依次在每个类别
+/// （成员按索引升序排列）上执行部分Fisher–Yates洗牌。所有类别共享同一个随机数流，
+/// 因此整个委员会只需要一轮贡献。
+///
+/// # 参数
+///
+/// * `values` - 表示参与者输入数据的u128值切片
+/// * `n` - 参与者数量（应与values.len()匹配，且必须是2的n次幂）
+/// * `categories` - 每位参与者的类别编号（长度应与n匹配，编号必须 < seats.len()）
+/// * `seats` - 每个类别的席位数（类别数 <= 64）
+/// * `scratch` - 临时缓冲区（长度必须 >= n）
+/// * `output` - 用于存储选中参与者索引的可变切片（长度应等于席位总数）；
+///   按类别顺序排列，前seats\[0\]个来自类别0，依此类推
+///
+/// # 返回值
+///
+/// * `Result<()>` - 成功时返回Ok；某个类别的成员少于其席位数时返回
+///   `StratumTooSmall { category }`（报告编号最小的此类类别）
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{select_stratified, Error};
+///
+/// let values = [5u128, 8, 13, 21, 34, 55, 89, 144];
+/// // 类别：0 = 北区，1 = 南区，2 = 专家
+/// let categories = [0usize, 1, 0, 1, 2, 0, 1, 2];
+/// let mut scratch = [0usize; 8];
+/// let mut output = [0usize; 4];
+/// select_stratified(&values, 8, &categories, &[2, 1, 1], &mut scratch, &mut output).unwrap();
+/// assert!(output[..2].iter().all(|&i| categories[i] == 0));
+/// assert_eq!(categories[output[2]], 1);
+/// assert_eq!(categories[output[3]], 2);
+///
+/// let mut output = [0usize; 3];
+/// let res = select_stratified(&values, 8, &categories, &[0, 0, 3], &mut scratch, &mut output);
+/// assert_eq!(res, Err(Error::StratumTooSmall { category: 2 }));
+/// ```
+pub fn select_stratified(
+    values: &[u128],
+    n: usize,
+    categories: &[usize],
+    seats: &[usize],
+    scratch: &mut [usize],
+    output: &mut [usize],
+) -> Result<()> {
+    // 验证输入参数
+    let strata = seats.len();
+    if strata == 0 || strata > MAX_STRATA || categories.len() != n || scratch.len() < n {
+        return Err(Error::InvalidInput);
+    }
+    let mut total_seats = 0usize;
+    for &s in seats {
+        total_seats = total_seats.checked_add(s).ok_or(Error::InvalidInput)?;
+    }
+    if total_seats == 0 || output.len() != total_seats {
+        return Err(Error::InvalidInput);
+    }
+    let mut rng = DdRng::from_contributions(values, n)?;
+
+    // 统计每个类别的成员数
+    let mut sizes = [0usize; MAX_STRATA];
+    for &c in categories {
+        if c >= strata {
+            return Err(Error::InvalidInput);
+        }
+        sizes[c] += 1;
+    }
+    for (c, (&size, &s)) in sizes.iter().zip(seats).enumerate() {
+        if size < s {
+            return Err(Error::StratumTooSmall { category: c });
+        }
+    }
+
+    // 计数排序：按类别分桶，桶内保持索引升序
+    let mut next = [0usize; MAX_STRATA];
+    let mut start = 0;
+    for (slot, &size) in next.iter_mut().zip(&sizes[..strata]) {
+        *slot = start;
+        start += size;
+    }
+    let bounds = next;
+    for (i, &c) in categories.iter().enumerate() {
+        scratch[next[c]] = i;
+        next[c] += 1;
+    }
+
+    // 依次在每个类别上抽取
+    let mut filled = 0;
+    for c in 0..strata {
+        let pool = &mut scratch[bounds[c]..bounds[c] + sizes[c]];
+        draw_distinct(&mut rng, pool, &mut output[filled..filled + seats[c]])?;
+        filled += seats[c];
+    }
+    Ok(())
+}
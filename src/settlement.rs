@@ -0,0 +1,284 @@
+//! 彩票结算与奖池分配。
+//!
+//! 将彩票与开奖号码比对并归入奖级，按固定奖金、奖池分成（pari-mutuel）和
+//! 可滚存头奖三种方式计算每张中奖彩票的奖金。所有除法都是精确的整数除法，
+//! 除不尽的余额一律滚存到下期，保证 已派奖金 + 滚存 = 本期奖池 + 上期滚存。
+
+use crate::lottery::{count_lottery_matches, match_3d_ticket, LotteryFormat, Play3d, Prize3d};
+use crate::math::mul_div;
+use crate::{Error, Result};
+
+/// 最大奖级数
+pub const MAX_PRIZE_TIERS: usize = 16;
+
+/// 一张彩票
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LotteryTicket<'a> {
+    /// 投注号码
+    pub numbers: &'a [u8],
+    /// 福彩3D投注方式（其他玩法忽略此字段）
+    pub play: Play3d,
+}
+
+/// 奖级的中奖条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TierMatch {
+    /// 匹配个数恰好为给定值（按`count_lottery_matches`统计）
+    Matches(usize),
+    /// 福彩3D中奖等级（按`match_3d_ticket`判定）
+    Fucai3d(Prize3d),
+}
+
+/// 奖级的奖金规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TierPayout {
+    /// 固定奖金：每张中奖彩票获得固定金额，优先从奖池支付
+    Fixed(u128),
+    /// 奖池分成：支付固定奖金后剩余奖池的万分之`share_bps`，由该奖级中奖者平分
+    PariMutuel {
+        /// 分成比例（万分之一）
+        share_bps: u32,
+    },
+    /// 头奖：与奖池分成相同，另加上期滚存；无人中奖时全部滚存到下期。每期至多一个头奖奖级
+    Jackpot {
+        /// 分成比例（万分之一）
+        share_bps: u32,
+    },
+}
+
+/// 奖级定义
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrizeTier {
+    /// 中奖条件
+    pub condition: TierMatch,
+    /// 奖金规则
+    pub payout: TierPayout,
+}
+
+/// 结算报告
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SettlementReport {
+    /// 每个奖级的中奖彩票数，仅前`tiers.len()`项有效
+    pub winners: [usize; MAX_PRIZE_TIERS],
+    /// 每个奖级实际派发的奖金总额，仅前`tiers.len()`项有效
+    pub paid: [u128; MAX_PRIZE_TIERS],
+    /// 本期派发的奖金总额
+    pub total_paid: u128,
+    /// 滚存到下期的金额（含未中出的头奖、无人中奖的奖池分成、除不尽的余额）
+    pub rollover: u128,
+    /// 奖池不足以支付全部固定奖金时为true，此时固定奖金按比例缩减
+    pub fixed_scaled: bool,
+}
+
+/// 判定一张彩票所属的奖级。
+///
+/// 按`tiers`的顺序检查，返回第一个满足条件的奖级索引；因此应将高奖级排在前面。
+///
+/// # 参数
+///
+/// * `ticket` - 彩票
+/// * `draw` - 开奖号码
+/// * `format` - 彩票玩法格式
+/// * `tiers` - 奖级定义
+///
+/// # 返回值
+///
+/// * `Result<Option<usize>>` - 成功时返回奖级索引，未中奖时返回None
+pub fn classify_lottery_ticket(
+    ticket: &LotteryTicket,
+    draw: &[u8],
+    format: LotteryFormat,
+    tiers: &[PrizeTier],
+) -> Result<Option<usize>> {
+    let mut matches: Option<usize> = None;
+    let mut prize_3d: Option<Prize3d> = None;
+
+    for (i, tier) in tiers.iter().enumerate() {
+        let hit = match tier.condition {
+            TierMatch::Matches(k) => {
+                let m = match matches {
+                    Some(m) => m,
+                    None => *matches.insert(count_lottery_matches(ticket.numbers, draw, format)?),
+                };
+                m == k
+            }
+            TierMatch::Fucai3d(prize) => {
+                if prize == Prize3d::NoPrize {
+                    return Err(Error::InvalidInput);
+                }
+                let p = match prize_3d {
+                    Some(p) => p,
+                    None => *prize_3d.insert(match_3d_ticket(ticket.numbers, draw, ticket.play)?),
+                };
+                p == prize
+            }
+        };
+        if hit {
+            return Ok(Some(i));
+        }
+    }
+    Ok(None)
+}
+
+/// 结算一期彩票并分配奖池。
+///
+/// # 分配顺序
+///
+/// 1. 逐张判定奖级，统计每个奖级的中奖彩票数
+/// 2. 从奖池中支付固定奖金；奖池不足时每张彩票按 奖池/固定奖金总额 的比例缩减（向下取整），
+///    此时奖池分成类奖级不再分配
+/// 3. 剩余奖池按各奖级的`share_bps`划分，头奖另加上期滚存
+/// 4. 每个奖级的金额由中奖者平分，每人得到 floor(奖级金额 / 中奖数)
+/// 5. 未分配的比例、无人中奖的奖级金额和平分余数全部滚存到下期
+///
+/// # 参数
+///
+/// * `tickets` - 本期所有彩票
+/// * `draw` - 开奖号码
+/// * `format` - 彩票玩法格式
+/// * `tiers` - 奖级定义（<= 16个，`share_bps`之和必须 <= 10,000，至多一个头奖奖级）
+/// * `pool` - 本期奖池
+/// * `rollover_in` - 上期滚存金额
+/// * `payouts` - 用于存储每张彩票奖金的可变切片（应与tickets.len()匹配）
+/// * `ticket_tiers` - 用于存储每张彩票奖级的可变切片（应与tickets.len()匹配）
+///
+/// # 返回值
+///
+/// * `Result<SettlementReport>` - 成功时返回结算报告
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{
+///     settle_lottery, LotteryFormat, LotteryTicket, Play3d, Prize3d, PrizeTier, TierMatch,
+///     TierPayout,
+/// };
+///
+/// let tiers = [
+///     PrizeTier { condition: TierMatch::Fucai3d(Prize3d::Straight), payout: TierPayout::Fixed(1040) },
+///     PrizeTier { condition: TierMatch::Fucai3d(Prize3d::Group6), payout: TierPayout::Fixed(173) },
+/// ];
+/// let tickets = [
+///     LotteryTicket { numbers: &[1, 2, 3], play: Play3d::Straight },
+///     LotteryTicket { numbers: &[3, 2, 1], play: Play3d::Box },
+///     LotteryTicket { numbers: &[4, 5, 6], play: Play3d::Straight },
+/// ];
+/// let mut payouts = [0u128; 3];
+/// let mut ticket_tiers = [None; 3];
+/// let report = settle_lottery(
+///     &tickets, &[1, 2, 3], LotteryFormat::FUCAI_3D, &tiers, 5_000, 0,
+///     &mut payouts, &mut ticket_tiers,
+/// ).unwrap();
+/// assert_eq!(payouts, [1040, 173, 0]);
+/// assert_eq!(report.total_paid + report.rollover, 5_000);
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn settle_lottery(
+    tickets: &[LotteryTicket],
+    draw: &[u8],
+    format: LotteryFormat,
+    tiers: &[PrizeTier],
+    pool: u128,
+    rollover_in: u128,
+    payouts: &mut [u128],
+    ticket_tiers: &mut [Option<usize>],
+) -> Result<SettlementReport> {
+    // 验证输入参数
+    if tiers.is_empty() || tiers.len() > MAX_PRIZE_TIERS {
+        return Err(Error::InvalidInput);
+    }
+    if payouts.len() != tickets.len() || ticket_tiers.len() != tickets.len() {
+        return Err(Error::InvalidInput);
+    }
+    let mut total_bps = 0u32;
+    let mut jackpots = 0usize;
+    for tier in tiers {
+        if let TierPayout::PariMutuel { share_bps } | TierPayout::Jackpot { share_bps } =
+            tier.payout
+        {
+            total_bps = total_bps.saturating_add(share_bps);
+        }
+        if let TierPayout::Jackpot { .. } = tier.payout {
+            jackpots += 1;
+        }
+    }
+    // 上期滚存只能计入一个头奖奖级
+    if total_bps > 10_000 || jackpots > 1 {
+        return Err(Error::InvalidInput);
+    }
+    let funds = pool
+        .checked_add(rollover_in)
+        .ok_or(Error::CalculationFailed)?;
+
+    // 1. 判定奖级
+    let mut report = SettlementReport {
+        winners: [0; MAX_PRIZE_TIERS],
+        paid: [0; MAX_PRIZE_TIERS],
+        total_paid: 0,
+        rollover: 0,
+        fixed_scaled: false,
+    };
+    for (ticket, slot) in tickets.iter().zip(ticket_tiers.iter_mut()) {
+        *slot = classify_lottery_ticket(ticket, draw, format, tiers)?;
+        if let Some(t) = *slot {
+            report.winners[t] += 1;
+        }
+    }
+
+    // 2. 固定奖金
+    let mut fixed_total = 0u128;
+    for (t, tier) in tiers.iter().enumerate() {
+        if let TierPayout::Fixed(amount) = tier.payout {
+            let due = amount
+                .checked_mul(report.winners[t] as u128)
+                .ok_or(Error::CalculationFailed)?;
+            fixed_total = fixed_total
+                .checked_add(due)
+                .ok_or(Error::CalculationFailed)?;
+        }
+    }
+    report.fixed_scaled = fixed_total > pool;
+
+    // 3. 各奖级的金额
+    let remaining = if report.fixed_scaled {
+        0
+    } else {
+        pool - fixed_total
+    };
+    let mut tier_amount = [0u128; MAX_PRIZE_TIERS];
+    for (t, tier) in tiers.iter().enumerate() {
+        tier_amount[t] = match tier.payout {
+            TierPayout::Fixed(_) => 0,
+            TierPayout::PariMutuel { share_bps } => {
+                mul_div(remaining, share_bps as u128, 10_000).ok_or(Error::CalculationFailed)?
+            }
+            TierPayout::Jackpot { share_bps } => mul_div(remaining, share_bps as u128, 10_000)
+                .and_then(|amount| amount.checked_add(rollover_in))
+                .ok_or(Error::CalculationFailed)?,
+        };
+    }
+
+    // 4. 逐张计算奖金
+    for (slot, payout) in ticket_tiers.iter().zip(payouts.iter_mut()) {
+        *payout = match *slot {
+            None => 0,
+            Some(t) => match tiers[t].payout {
+                TierPayout::Fixed(amount) if report.fixed_scaled => {
+                    mul_div(amount, pool, fixed_total).ok_or(Error::CalculationFailed)?
+                }
+                TierPayout::Fixed(amount) => amount,
+                _ => tier_amount[t] / report.winners[t] as u128,
+            },
+        };
+        if let Some(t) = *slot {
+            report.paid[t] += *payout;
+            report.total_paid += *payout;
+        }
+    }
+
+    // 5. 其余全部滚存
+    report.rollover = funds
+        .checked_sub(report.total_paid)
+        .ok_or(Error::CalculationFailed)?;
+    Ok(report)
+}
@@ -0,0 +1,273 @@
+//! Shamir秘密共享与Feldman可验证承诺。
+//!
+//! 承诺-揭示协议中，参与者可以在看到他人揭示的值之后拒绝揭示自己的值（中止攻击）。
+//! 本模块让每位贡献者在承诺阶段就把自己的值按t-of-n门限拆分给其他参与者，
+//! 并公布Feldman承诺；任何人都可以验证收到的份额，若贡献者拒绝揭示，
+//! 任意t个诚实参与者即可恢复其值，再送入`get_one_dd_rand_num`。
+//!
+//! # 参数选择
+//!
+//! * 秘密和份额属于素数域 Z_q，q = 2^126 - 1361
+//! * 承诺位于安全素数 p = 2q + 1 = 2^127 - 2721 的q阶子群中，生成元 g = 4
+//!
+//! 所有运算都只使用u128整数，适用于`no_std`和合约环境。
+//!
+//! # 安全性
+//!
+//! Feldman承诺不是隐藏承诺：C_0 = g^secret 在承诺阶段就公开，而127位素数群上的
+//! 离散对数用数域筛法是可以求解的，能承担这一计算的人可以在揭示之前得到每位贡献者的秘密，
+//! 重新引入承诺-揭示本应阻止的"最后揭示者"攻击。因此这里的承诺只用于检查份额的一致性，
+//! 只应在不要求贡献值在揭示前保密的场合使用（例如承诺只发给份额持有人、且他们不会合谋）。
+//! 需要在揭示前隐藏贡献值时，使用`pedersen`模块（`pedersen`特性）中基于ristretto255的
+//! Pedersen承诺，它对秘密是无条件隐藏的。
+
+use crate::math::{mul_mod, pow_mod};
+use crate::{Error, Result};
+
+/// 秘密与份额所在素数域的模数 q = 2^126 - 1361
+pub const SHAMIR_FIELD_PRIME: u128 = 0x3fff_ffff_ffff_ffff_ffff_ffff_ffff_faaf;
+
+/// Feldman承诺所在群的模数 p = 2q + 1 = 2^127 - 2721（安全素数）
+pub const FELDMAN_GROUP_PRIME: u128 = 0x7fff_ffff_ffff_ffff_ffff_ffff_ffff_f55f;
+
+/// q阶子群的生成元
+pub const FELDMAN_GENERATOR: u128 = 4;
+
+/// 最大份额数
+pub const MAX_SHAMIR_SHARES: usize = 256;
+
+const Q: u128 = SHAMIR_FIELD_PRIME;
+const P: u128 = FELDMAN_GROUP_PRIME;
+
+/// 一个秘密份额：f(index)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ShamirShare {
+    /// 份额编号 x（从1开始，0保留给秘密本身）
+    pub index: u64,
+    /// 份额值 f(x) mod q
+    pub value: u128,
+}
+
+#[inline]
+fn add_q(a: u128, b: u128) -> u128 {
+    // a, b < q < 2^126，不会溢出
+    (a + b) % Q
+}
+
+#[inline]
+fn sub_q(a: u128, b: u128) -> u128 {
+    (a + Q - b) % Q
+}
+
+#[inline]
+fn inv_q(a: u128) -> u128 {
+    // 费马小定理：a^(q-2) = a^(-1) mod q
+    pow_mod(a, Q - 2, Q)
+}
+
+/// 将秘密拆分为n个份额，任意t个份额可以恢复秘密，并生成Feldman承诺。
+///
+/// 多项式 f(x) = secret + c_1·x + ... + c_{t-1}·x^{t-1}（mod q），第i个份额为 (i, f(i))，
+/// 承诺为 C_j = g^{c_j} mod p（C_0 = g^secret）。承诺不隐藏秘密，见模块文档中的安全性说明。
+///
+/// # 参数
+///
+/// * `secret` - 要共享的秘密（必须 < q）
+/// * `coefficients` - t-1个随机系数（每个必须 < q），由贡献者从自己的私有随机源取得
+/// * `shares` - 用于存储n个份额的可变切片（t <= n <= 256），编号依次为1..=n
+/// * `commitments` - 用于存储t个Feldman承诺的可变切片
+///
+/// # 返回值
+///
+/// * `Result<()>` - 成功时返回Ok，输入无效时返回InvalidInput
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{
+///     feldman_verify_share, get_one_dd_rand_num, shamir_reconstruct_verified, shamir_split,
+///     ShamirShare,
+/// };
+///
+/// // 参与者2承诺了值，但拒绝揭示
+/// let withheld = 0x1234_5678_9abc_def0u128;
+/// let mut shares = [ShamirShare::default(); 5];
+/// let mut commitments = [0u128; 3];
+/// shamir_split(withheld, &[111, 222], &mut shares, &mut commitments).unwrap();
+/// assert!(shares.iter().all(|s| feldman_verify_share(s, &commitments).unwrap()));
+///
+/// // 任意3个参与者交出份额即可恢复
+/// let recovered = shamir_reconstruct_verified(&shares[2..], &commitments).unwrap();
+/// assert_eq!(recovered, withheld);
+///
+/// let values = [10u128, 20, recovered, 40];
+/// let mut out = 0u128;
+/// get_one_dd_rand_num(&values, 4, &mut out).unwrap();
+/// ```
+pub fn shamir_split(
+    secret: u128,
+    coefficients: &[u128],
+    shares: &mut [ShamirShare],
+    commitments: &mut [u128],
+) -> Result<()> {
+    // 验证输入参数
+    let t = coefficients.len() + 1;
+    let n = shares.len();
+    if n < t || n > MAX_SHAMIR_SHARES || commitments.len() != t {
+        return Err(Error::InvalidInput);
+    }
+    if secret >= Q || coefficients.iter().any(|&c| c >= Q) {
+        return Err(Error::InvalidInput);
+    }
+
+    // 霍纳法计算 f(x)
+    for (i, share) in shares.iter_mut().enumerate() {
+        let x = i as u128 + 1;
+        let mut y = 0u128;
+        for &c in coefficients.iter().rev() {
+            y = add_q(mul_mod(y, x, Q), c);
+        }
+        y = add_q(mul_mod(y, x, Q), secret);
+        *share = ShamirShare {
+            index: x as u64,
+            value: y,
+        };
+    }
+
+    commitments[0] = pow_mod(FELDMAN_GENERATOR, secret, P);
+    for (c, &a) in commitments[1..].iter_mut().zip(coefficients) {
+        *c = pow_mod(FELDMAN_GENERATOR, a, P);
+    }
+    Ok(())
+}
+
+/// 使用Feldman承诺验证一个份额：g^{f(i)} = Π C_j^{i^j} (mod p)。
+///
+/// # 参数
+///
+/// * `share` - 要验证的份额
+/// * `commitments` - 贡献者公布的t个承诺
+///
+/// # 返回值
+///
+/// * `Result<bool>` - 份额与承诺一致时返回true；承诺不是q阶子群中的元素时返回InvalidInput
+pub fn feldman_verify_share(share: &ShamirShare, commitments: &[u128]) -> Result<bool> {
+    if commitments.is_empty() || commitments.len() > MAX_SHAMIR_SHARES {
+        return Err(Error::InvalidInput);
+    }
+    for &c in commitments {
+        if c == 0 || c >= P || pow_mod(c, Q, P) != 1 {
+            return Err(Error::InvalidInput);
+        }
+    }
+    if share.index == 0 || share.value >= Q {
+        return Ok(false);
+    }
+
+    // 指数上的霍纳法：((C_{t-1})^i · C_{t-2})^i · ... · C_0
+    let x = share.index as u128;
+    let mut acc = 1u128;
+    for &c in commitments.iter().rev() {
+        acc = mul_mod(pow_mod(acc, x, P), c, P);
+    }
+    Ok(pow_mod(FELDMAN_GENERATOR, share.value, P) == acc)
+}
+
+/// 对`shares`中被`used`选中的份额做拉格朗日插值，返回 f(0)
+fn interpolate_at_zero(shares: &[ShamirShare], used: &[bool]) -> Result<u128> {
+    let mut secret = 0u128;
+    for (i, si) in shares.iter().enumerate() {
+        if !used[i] {
+            continue;
+        }
+        let xi = si.index as u128 % Q;
+        let mut num = 1u128;
+        let mut den = 1u128;
+        for (j, sj) in shares.iter().enumerate() {
+            if j == i || !used[j] {
+                continue;
+            }
+            let xj = sj.index as u128 % Q;
+            if xj == xi {
+                return Err(Error::InvalidInput);
+            }
+            num = mul_mod(num, xj, Q);
+            den = mul_mod(den, sub_q(xj, xi), Q);
+        }
+        let basis = mul_mod(num, inv_q(den), Q);
+        secret = add_q(secret, mul_mod(si.value % Q, basis, Q));
+    }
+    Ok(secret)
+}
+
+/// 由份额恢复秘密（拉格朗日插值）。
+///
+/// 使用全部给出的份额；份额数不少于t且都正确时结果等于秘密。
+/// 不验证份额，存在恶意份额时请使用`shamir_reconstruct_verified`。
+///
+/// # 参数
+///
+/// * `shares` - 份额（编号必须非0且互不相同，数量 <= 256）
+///
+/// # 返回值
+///
+/// * `Result<u128>` - 成功时返回恢复的秘密
+pub fn shamir_reconstruct(shares: &[ShamirShare]) -> Result<u128> {
+    if shares.is_empty() || shares.len() > MAX_SHAMIR_SHARES || shares.iter().any(|s| s.index == 0)
+    {
+        return Err(Error::InvalidInput);
+    }
+    let used = [true; MAX_SHAMIR_SHARES];
+    interpolate_at_zero(shares, &used[..shares.len()])
+}
+
+/// 验证份额后恢复秘密，并用承诺C_0检查结果。
+///
+/// 跳过未通过Feldman验证的份额，取前t个有效份额插值（t = commitments.len()），
+/// 最后检查 g^secret = C_0。
+///
+/// # 参数
+///
+/// * `shares` - 收集到的份额（数量 <= 256）
+/// * `commitments` - 贡献者公布的t个承诺
+///
+/// # 返回值
+///
+/// * `Result<u128>` - 成功时返回恢复的秘密；有效份额少于t时返回NotEnoughParticipants
+pub fn shamir_reconstruct_verified(shares: &[ShamirShare], commitments: &[u128]) -> Result<u128> {
+    if shares.len() > MAX_SHAMIR_SHARES {
+        return Err(Error::InvalidInput);
+    }
+    if commitments.is_empty() || commitments.len() > MAX_SHAMIR_SHARES {
+        return Err(Error::InvalidInput);
+    }
+    let t = commitments.len();
+    let mut used = [false; MAX_SHAMIR_SHARES];
+    let mut valid = 0usize;
+    for (i, share) in shares.iter().enumerate() {
+        if valid == t {
+            break;
+        }
+        // 跳过编号重复的份额
+        if shares[..i]
+            .iter()
+            .zip(&used[..i])
+            .any(|(s, &u)| u && s.index == share.index)
+        {
+            continue;
+        }
+        if feldman_verify_share(share, commitments)? {
+            used[i] = true;
+            valid += 1;
+        }
+    }
+    if valid < t {
+        return Err(Error::NotEnoughParticipants);
+    }
+
+    let secret = interpolate_at_zero(shares, &used[..shares.len()])?;
+    if pow_mod(FELDMAN_GENERATOR, secret, P) != commitments[0] {
+        return Err(Error::CalculationFailed);
+    }
+    Ok(secret)
+}
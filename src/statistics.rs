@@ -0,0 +1,327 @@
+//! 随机性统计检验。
+//!
+//! 合法性和唯一性检查无法发现分布上的偏差。本模块提供可在`cargo test`或审计脚本中
+//! 复用的统计检验，用于检验各随机数接口（及信标的各组合器）的输出分布：
+//!
+//! * `chi_square_uniform` - 卡方拟合优度检验（分箱计数是否均匀）
+//! * `ks_uniform` - Kolmogorov–Smirnov检验（u128样本是否在 [0, 2^128) 上均匀）
+//! * `serial_correlation` - 相邻样本的一阶序列相关检验
+//! * `selection_frequency` - `get_k_dd_rand_num`等不放回选择中各索引的被选频率检验
+//! * `randomness_suite` - 对一组u128样本同时运行以上前三项检验
+//!
+//! # 接受阈值
+//!
+//! 所有检验的显著性水平均为 α = 0.001（`STATS_ALPHA_PPM`），即对真正均匀的随机源，
+//! 单项检验误判失败的概率为千分之一。临界值：
+//!
+//! * 卡方：Wilson–Hilferty近似 df·(1 - h + z·√h)³，h = 2/(9·df)，z = 3.0902（单侧）
+//! * KS：1.9495 / (√n + 0.12 + 0.11/√n)（Stephens修正）
+//! * 序列相关：|r| <= 3.2905 / √n（双侧）
+//!
+//! 统计量与临界值均为定点数，精度为`STATS_SCALE`。使用确定性种子时检验结果可复现。
+
+use crate::math::{isqrt, mul_div};
+use crate::{Error, Result};
+
+/// 统计量定点数精度：1.0 = `STATS_SCALE`
+pub const STATS_SCALE: u128 = 1_000_000_000;
+
+/// 检验的显著性水平（百万分之一）：0.001
+pub const STATS_ALPHA_PPM: u32 = 1_000;
+
+/// `randomness_suite`支持的最大分箱数
+pub const MAX_STATS_BINS: usize = 1024;
+
+const S: u128 = STATS_SCALE;
+
+/// 标准正态分布 0.999 分位数（单侧）
+const Z_ONE_SIDED: u128 = 3_090_232_306;
+
+/// 标准正态分布 0.9995 分位数（双侧）
+const Z_TWO_SIDED: u128 = 3_290_526_731;
+
+/// Kolmogorov分布 0.999 分位数
+const KS_C: u128 = 1_949_500_000;
+
+/// 单项检验结果（定点数，精度为`STATS_SCALE`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatTestResult {
+    /// 检验统计量
+    pub statistic: u128,
+    /// α = 0.001 时的临界值
+    pub critical_value: u128,
+    /// statistic <= critical_value
+    pub passed: bool,
+}
+
+impl StatTestResult {
+    fn new(statistic: u128, critical_value: u128) -> Self {
+        StatTestResult {
+            statistic,
+            critical_value,
+            passed: statistic <= critical_value,
+        }
+    }
+}
+
+/// `randomness_suite`的检验报告
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RandomnessReport {
+    /// 卡方拟合优度检验
+    pub chi_square: StatTestResult,
+    /// Kolmogorov–Smirnov检验
+    pub kolmogorov_smirnov: StatTestResult,
+    /// 一阶序列相关检验
+    pub serial_correlation: StatTestResult,
+}
+
+impl RandomnessReport {
+    /// 三项检验是否全部通过
+    pub fn passed(&self) -> bool {
+        self.chi_square.passed && self.kolmogorov_smirnov.passed && self.serial_correlation.passed
+    }
+}
+
+/// √n（定点数）
+fn sqrt_scaled(n: u128) -> Result<u128> {
+    let n_s2 = n.checked_mul(S * S).ok_or(Error::InvalidInput)?;
+    Ok(isqrt(n_s2))
+}
+
+/// 样本在 [0, 1) 上的位置（定点数），取高64位
+#[inline]
+fn unit_scaled(x: u128) -> u128 {
+    ((x >> 64) * S) >> 64
+}
+
+/// 卡方分布在 α = 0.001 时的临界值（Wilson–Hilferty近似，定点数）。
+///
+/// df >= 3 时相对误差小于1%，df较小时略偏保守。
+///
+/// # 参数
+///
+/// * `df` - 自由度（必须 > 0）
+///
+/// # 返回值
+///
+/// * `Result<u128>` - 成功时返回临界值，df为0时返回InvalidInput
+pub fn chi_square_critical_value(df: u64) -> Result<u128> {
+    if df == 0 {
+        return Err(Error::InvalidInput);
+    }
+    let df = df as u128;
+    let h = 2 * S / (9 * df);
+    let sqrt_h = isqrt(h * S);
+    let term = S - h + Z_ONE_SIDED * sqrt_h / S;
+    let cube = (term * term / S) * term / S;
+    cube.checked_mul(df).ok_or(Error::InvalidInput)
+}
+
+/// 卡方统计量 Σ(O·B - N)² / (N·B)（定点数）
+fn chi_square_statistic(counts: &[u64]) -> Result<u128> {
+    let bins = counts.len() as u128;
+    let total: u128 = counts.iter().map(|&c| c as u128).sum();
+    if bins < 2 || total == 0 {
+        return Err(Error::InvalidInput);
+    }
+    let denominator = total.checked_mul(bins).ok_or(Error::InvalidInput)?;
+    let mut statistic = 0u128;
+    for &c in counts {
+        let d = (c as u128 * bins).abs_diff(total);
+        let d_scaled = d.checked_mul(S).ok_or(Error::InvalidInput)?;
+        let term = mul_div(d_scaled, d, denominator).ok_or(Error::InvalidInput)?;
+        statistic = statistic.checked_add(term).ok_or(Error::InvalidInput)?;
+    }
+    Ok(statistic)
+}
+
+/// 卡方拟合优度检验：各分箱的计数是否服从均匀分布。
+///
+/// 自由度为 分箱数 - 1。为保证近似有效，每个分箱的期望计数应不少于5。
+///
+/// # 参数
+///
+/// * `counts` - 各分箱的观测计数（至少2个分箱，总数必须 > 0）
+///
+/// # 返回值
+///
+/// * `Result<StatTestResult>` - 成功时返回检验结果，输入无效或数值溢出时返回InvalidInput
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::chi_square_uniform;
+///
+/// assert!(chi_square_uniform(&[98, 103, 101, 97, 100, 101]).unwrap().passed);
+/// assert!(!chi_square_uniform(&[200, 100, 100, 100, 100, 0]).unwrap().passed);
+/// ```
+pub fn chi_square_uniform(counts: &[u64]) -> Result<StatTestResult> {
+    let statistic = chi_square_statistic(counts)?;
+    let critical = chi_square_critical_value(counts.len() as u64 - 1)?;
+    Ok(StatTestResult::new(statistic, critical))
+}
+
+/// Kolmogorov–Smirnov检验：u128样本是否在 [0, 2^128) 上均匀分布。
+///
+/// 样本被视为 [0, 1) 上的小数（取高64位），统计量为经验分布函数与均匀分布函数的
+/// 最大距离 D。函数会对`samples`原地排序。
+///
+/// # 参数
+///
+/// * `samples` - 样本（至少2个）
+///
+/// # 返回值
+///
+/// * `Result<StatTestResult>` - 成功时返回检验结果，输入无效时返回InvalidInput
+pub fn ks_uniform(samples: &mut [u128]) -> Result<StatTestResult> {
+    let n = samples.len() as u128;
+    if n < 2 {
+        return Err(Error::InvalidInput);
+    }
+    let sqrt_n = sqrt_scaled(n)?;
+    samples.sort_unstable();
+
+    let mut d = 0u128;
+    for (i, &x) in samples.iter().enumerate() {
+        let u = unit_scaled(x);
+        let i = i as u128;
+        let below = i * S / n;
+        let above = (i + 1) * S / n;
+        d = d.max(above.saturating_sub(u)).max(u.saturating_sub(below));
+    }
+
+    // c / (√n + 0.12 + 0.11/√n)
+    let denominator = sqrt_n + 120_000_000 + 110_000_000 * S / sqrt_n;
+    let critical = KS_C * S / denominator;
+    Ok(StatTestResult::new(d, critical))
+}
+
+/// 一阶序列相关检验：相邻样本之间是否线性相关。
+///
+/// 按Knuth的循环定义计算 r = (n·Σuᵢuᵢ₊₁ - (Σuᵢ)²) / (n·Σuᵢ² - (Σuᵢ)²)，
+/// uᵢ取样本的高32位。统计量为 |r|。
+///
+/// # 参数
+///
+/// * `samples` - 按生成顺序排列的样本（2 <= 数量 <= 2^31）
+///
+/// # 返回值
+///
+/// * `Result<StatTestResult>` - 成功时返回检验结果；输入无效时返回InvalidInput，
+///   所有样本相同（方差为0）时返回CalculationFailed
+pub fn serial_correlation(samples: &[u128]) -> Result<StatTestResult> {
+    let n = samples.len() as u128;
+    if !(2..=1 << 31).contains(&n) {
+        return Err(Error::InvalidInput);
+    }
+    let mut sum = 0u128;
+    let mut sum_sq = 0u128;
+    let mut sum_lag = 0u128;
+    for (i, &x) in samples.iter().enumerate() {
+        let u = x >> 96;
+        let next = samples[(i + 1) % samples.len()] >> 96;
+        sum += u;
+        sum_sq += u * u;
+        sum_lag += u * next;
+    }
+    let sum_squared = sum * sum;
+    let denominator = n * sum_sq - sum_squared;
+    if denominator == 0 {
+        return Err(Error::CalculationFailed);
+    }
+    let numerator = (n * sum_lag).abs_diff(sum_squared);
+    let statistic = mul_div(numerator, S, denominator).ok_or(Error::CalculationFailed)?;
+
+    let critical = Z_TWO_SIDED * S / sqrt_scaled(n)?;
+    Ok(StatTestResult::new(statistic, critical))
+}
+
+/// 不放回选择的各索引被选频率检验。
+///
+/// 每次从n个索引中不重复地选出k个，重复多次后统计每个索引被选中的次数。
+/// 对均匀的选择，Σ(Oᵢ - E)²/E 的期望为 n - k；乘以 (n - 1)/(n - k) 后近似服从
+/// 自由度为 n - 1 的卡方分布（k = 1 时即普通的卡方检验）。
+///
+/// # 参数
+///
+/// * `counts` - 每个索引被选中的次数（长度n >= 2）
+/// * `k` - 每次选择的数量（0 < k < n，计数总和必须是k的倍数）
+///
+/// # 返回值
+///
+/// * `Result<StatTestResult>` - 成功时返回检验结果，输入无效时返回InvalidInput
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{get_k_dd_rand_num, selection_frequency, DdRng};
+///
+/// // 确定性种子：用DdRng模拟参与者的贡献值
+/// let mut rng = DdRng::new(42);
+/// let mut counts = [0u64; 8];
+/// for _ in 0..500 {
+///     let rows: Vec<[u128; 2]> = (0..8).map(|_| [rng.next_u128(), rng.next_u128()]).collect();
+///     let groups: Vec<&[u128]> = rows.iter().map(|r| r.as_slice()).collect();
+///     let mut output = [0usize; 2];
+///     get_k_dd_rand_num(&groups, 8, 2, &mut output).unwrap();
+///     for &i in &output {
+///         counts[i] += 1;
+///     }
+/// }
+/// assert!(selection_frequency(&counts, 2).unwrap().passed);
+/// ```
+pub fn selection_frequency(counts: &[u64], k: usize) -> Result<StatTestResult> {
+    let n = counts.len();
+    let total: u128 = counts.iter().map(|&c| c as u128).sum();
+    if n < 2 || k == 0 || k >= n || total % k as u128 != 0 {
+        return Err(Error::InvalidInput);
+    }
+    let statistic = chi_square_statistic(counts)?;
+    let statistic =
+        mul_div(statistic, (n - 1) as u128, (n - k) as u128).ok_or(Error::InvalidInput)?;
+    let critical = chi_square_critical_value(n as u64 - 1)?;
+    Ok(StatTestResult::new(statistic, critical))
+}
+
+/// 对一组u128样本运行卡方、KS和序列相关检验。
+///
+/// 卡方检验将 [0, 2^128) 等分为`bins`个分箱（按高64位）；序列相关检验按样本的
+/// 原始顺序计算，之后KS检验会对`samples`原地排序。
+///
+/// # 参数
+///
+/// * `samples` - 按生成顺序排列的样本（数量必须 >= 5 * bins）
+/// * `bins` - 卡方检验的分箱数（2 <= bins <= 1024）
+///
+/// # 返回值
+///
+/// * `Result<RandomnessReport>` - 成功时返回检验报告，输入无效时返回InvalidInput
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{randomness_suite, DdRng};
+///
+/// let mut rng = DdRng::new(0x5eed);
+/// let mut samples: Vec<u128> = (0..2048).map(|_| rng.next_u128()).collect();
+/// let report = randomness_suite(&mut samples, 32).unwrap();
+/// assert!(report.passed());
+/// ```
+pub fn randomness_suite(samples: &mut [u128], bins: usize) -> Result<RandomnessReport> {
+    if !(2..=MAX_STATS_BINS).contains(&bins) || samples.len() < 5 * bins {
+        return Err(Error::InvalidInput);
+    }
+    let mut counts = [0u64; MAX_STATS_BINS];
+    for &x in samples.iter() {
+        let bin = (((x >> 64) * bins as u128) >> 64) as usize;
+        counts[bin] += 1;
+    }
+    let chi_square = chi_square_uniform(&counts[..bins])?;
+    let serial_correlation = serial_correlation(samples)?;
+    let kolmogorov_smirnov = ks_uniform(samples)?;
+    Ok(RandomnessReport {
+        chi_square,
+        kolmogorov_smirnov,
+        serial_correlation,
+    })
+}
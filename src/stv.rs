@@ -0,0 +1,515 @@
+//! 单一可转移投票（STV）多席位比例代表选举。
+//!
+//! 所有计票都使用定点整数运算（见 [`STV_SCALE`]），相同输入在任何节点上
+//! 都得到完全相同的当选结果和计票记录。
+
+use crate::math::{mul_div, mul_div_ceil};
+use crate::types::VotingPower;
+use crate::{Error, Result};
+
+/// 最大候选人数
+pub const MAX_STV_CANDIDATES: usize = 64;
+
+/// 最大选票数（相同排序的选票应合并并累加权重）
+pub const MAX_STV_BALLOTS: usize = 1_000;
+
+/// 定点数精度：1票 = `STV_SCALE` 个计票单位
+pub const STV_SCALE: u128 = 1_000_000_000;
+
+/// Meek方法每轮的最大迭代次数
+const MEEK_MAX_ITERATIONS: usize = 1_000;
+
+/// Meek方法的收敛容差（计票单位，即0.0001票）
+const MEEK_TOLERANCE: u128 = STV_SCALE / 10_000;
+
+/// 已耗尽选票的位置标记
+const EXHAUSTED: usize = usize::MAX;
+
+/// 盈余票转移方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StvTransfer {
+    /// 加权包含式Gregory方法：当选者的全部选票按 盈余/得票 的比例转移
+    Gregory,
+    /// Meek方法：为当选者迭代计算保留系数，盈余在所有后续偏好间重新分配
+    Meek,
+}
+
+/// 单轮计票动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StvAction {
+    /// 候选人达到当选份额（或因剩余席位足够而当选）
+    Elected,
+    /// 得票最低的候选人被淘汰
+    Eliminated,
+}
+
+/// 单轮计票记录
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StvRound {
+    /// 轮次（从1开始）
+    pub round: usize,
+    /// 本轮动作
+    pub action: StvAction,
+    /// 本轮当选或被淘汰的候选人索引
+    pub candidate: usize,
+    /// 本轮使用的当选份额（计票单位）
+    pub quota: u128,
+    /// 从该候选人转出的票值（当选时为盈余，淘汰时为全部得票）
+    pub transferred: u128,
+    /// 截至本轮已耗尽的累计票值
+    pub exhausted: u128,
+    /// 本轮作出决定时各候选人的得票（计票单位），仅前`num_candidates`项有效
+    pub tallies: [u128; MAX_STV_CANDIDATES],
+}
+
+impl StvRound {
+    const EMPTY: StvRound = StvRound {
+        round: 0,
+        action: StvAction::Eliminated,
+        candidate: 0,
+        quota: 0,
+        transferred: 0,
+        exhausted: 0,
+        tallies: [0; MAX_STV_CANDIDATES],
+    };
+}
+
+impl Default for StvRound {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Hopeful,
+    Elected,
+    Excluded,
+}
+
+/// 使用单一可转移投票（Droop份额）进行多席位选举。
+///
+/// 每张选票是按偏好排序的候选人索引列表，并带有`VotingPower`权重。
+/// 当选份额为Droop份额 floor(有效票/(seats+1)) + 1（计票单位）。
+/// 每轮要么当选一名达到份额的候选人并转移其盈余，要么淘汰得票最低的候选人。
+///
+/// # 平局处理
+///
+/// * 多名候选人同时达到份额时，得票高者优先当选，得票相同则索引小者优先
+/// * 淘汰时得票最低者出局，得票相同则索引大者出局
+///
+/// # 参数
+///
+/// * `ballots` - 选票切片，每张选票是按偏好排序的候选人索引（不可重复）
+/// * `weights` - 每张选票的投票权重
+/// * `num_candidates` - 候选人数（必须 <= 64）
+/// * `seats` - 席位数（1 <= seats <= num_candidates，应与elected.len()匹配）
+/// * `method` - 盈余转移方法
+/// * `elected` - 按当选顺序存储当选候选人索引的可变切片
+/// * `transcript` - 用于存储逐轮计票记录的可变切片（长度必须 >= num_candidates）
+///
+/// # 返回值
+///
+/// * `Result<usize>` - 成功时返回写入`transcript`的轮数
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{calculate_stv_election, StvRound, StvTransfer};
+///
+/// let b0 = [0usize, 1];
+/// let b1 = [1usize, 0];
+/// let b2 = [2usize, 1];
+/// let ballots = [b0.as_slice(), b1.as_slice(), b2.as_slice()];
+/// let weights = [60u128, 25, 15];
+/// let mut elected = [0usize; 2];
+/// let mut transcript = [StvRound::default(); 3];
+/// let rounds = calculate_stv_election(
+///     &ballots, &weights, 3, 2, StvTransfer::Gregory, &mut elected, &mut transcript,
+/// ).unwrap();
+/// assert_eq!(elected, [0, 1]);
+/// assert_eq!(transcript[0].candidate, 0);
+/// assert!(rounds <= 3);
+/// ```
+pub fn calculate_stv_election(
+    ballots: &[&[usize]],
+    weights: &[VotingPower],
+    num_candidates: usize,
+    seats: usize,
+    method: StvTransfer,
+    elected: &mut [usize],
+    transcript: &mut [StvRound],
+) -> Result<usize> {
+    // 验证输入参数
+    if ballots.is_empty() || ballots.len() != weights.len() || ballots.len() > MAX_STV_BALLOTS {
+        return Err(Error::InvalidInput);
+    }
+    if num_candidates == 0 || num_candidates > MAX_STV_CANDIDATES {
+        return Err(Error::InvalidInput);
+    }
+    if seats == 0 || seats > num_candidates || elected.len() != seats {
+        return Err(Error::InvalidInput);
+    }
+    if transcript.len() < num_candidates {
+        return Err(Error::InvalidInput);
+    }
+
+    // 检查每张选票的候选人索引有效且不重复
+    for ballot in ballots {
+        let mut seen = [false; MAX_STV_CANDIDATES];
+        for &c in ballot.iter() {
+            if c >= num_candidates || seen[c] {
+                return Err(Error::InvalidInput);
+            }
+            seen[c] = true;
+        }
+    }
+
+    // 将权重转换为定点数并计算总票值
+    let mut ballot_weight = [0u128; MAX_STV_BALLOTS];
+    let mut total = 0u128;
+    for (i, &w) in weights.iter().enumerate() {
+        let fp = w.checked_mul(STV_SCALE).ok_or(Error::CalculationFailed)?;
+        ballot_weight[i] = fp;
+        total = total.checked_add(fp).ok_or(Error::CalculationFailed)?;
+    }
+    if total == 0 {
+        return Err(Error::InvalidInput);
+    }
+
+    let mut state = Count {
+        ballots,
+        num_candidates,
+        seats,
+        status: [Status::Hopeful; MAX_STV_CANDIDATES],
+        elected_count: 0,
+        rounds: 0,
+        exhausted: 0,
+    };
+
+    match method {
+        StvTransfer::Gregory => {
+            state.run_gregory(&mut ballot_weight, total, elected, transcript)?
+        }
+        StvTransfer::Meek => state.run_meek(&ballot_weight, total, elected, transcript)?,
+    }
+
+    Ok(state.rounds)
+}
+
+/// 计票过程中的共享状态
+struct Count<'a> {
+    ballots: &'a [&'a [usize]],
+    num_candidates: usize,
+    seats: usize,
+    status: [Status; MAX_STV_CANDIDATES],
+    elected_count: usize,
+    rounds: usize,
+    exhausted: u128,
+}
+
+impl Count<'_> {
+    fn record(
+        &mut self,
+        transcript: &mut [StvRound],
+        action: StvAction,
+        candidate: usize,
+        quota: u128,
+        transferred: u128,
+        tallies: &[u128; MAX_STV_CANDIDATES],
+    ) {
+        transcript[self.rounds] = StvRound {
+            round: self.rounds + 1,
+            action,
+            candidate,
+            quota,
+            transferred,
+            exhausted: self.exhausted,
+            tallies: *tallies,
+        };
+        self.rounds += 1;
+    }
+
+    fn elect(&mut self, candidate: usize, elected: &mut [usize]) {
+        self.status[candidate] = Status::Elected;
+        elected[self.elected_count] = candidate;
+        self.elected_count += 1;
+    }
+
+    fn hopeful_count(&self) -> usize {
+        self.status[..self.num_candidates]
+            .iter()
+            .filter(|&&s| s == Status::Hopeful)
+            .count()
+    }
+
+    /// 得票最高的候选人（得票相同则索引小者）
+    fn best_hopeful(&self, tallies: &[u128; MAX_STV_CANDIDATES]) -> Option<usize> {
+        let mut best: Option<usize> = None;
+        for c in 0..self.num_candidates {
+            if self.status[c] != Status::Hopeful {
+                continue;
+            }
+            match best {
+                Some(b) if tallies[c] <= tallies[b] => {}
+                _ => best = Some(c),
+            }
+        }
+        best
+    }
+
+    /// 得票最低的候选人（得票相同则索引大者）
+    fn worst_hopeful(&self, tallies: &[u128; MAX_STV_CANDIDATES]) -> Option<usize> {
+        let mut worst: Option<usize> = None;
+        for c in 0..self.num_candidates {
+            if self.status[c] != Status::Hopeful {
+                continue;
+            }
+            match worst {
+                Some(w) if tallies[c] > tallies[w] => {}
+                _ => worst = Some(c),
+            }
+        }
+        worst
+    }
+
+    /// 剩余候选人不多于剩余席位时，按得票从高到低全部当选
+    fn elect_remaining(
+        &mut self,
+        quota: u128,
+        tallies: &[u128; MAX_STV_CANDIDATES],
+        elected: &mut [usize],
+        transcript: &mut [StvRound],
+    ) {
+        while self.elected_count < self.seats {
+            let Some(c) = self.best_hopeful(tallies) else {
+                break;
+            };
+            self.elect(c, elected);
+            self.record(transcript, StvAction::Elected, c, quota, 0, tallies);
+        }
+    }
+
+    /// 从`from`位置开始找到选票上下一个仍在竞争的候选人位置
+    fn next_hopeful(&self, ballot: usize, from: usize) -> usize {
+        let prefs = self.ballots[ballot];
+        for (pos, &c) in prefs.iter().enumerate().skip(from) {
+            if self.status[c] == Status::Hopeful {
+                return pos;
+            }
+        }
+        EXHAUSTED
+    }
+
+    fn run_gregory(
+        &mut self,
+        weight: &mut [u128; MAX_STV_BALLOTS],
+        total: u128,
+        elected: &mut [usize],
+        transcript: &mut [StvRound],
+    ) -> Result<()> {
+        let quota = total / (self.seats as u128 + 1) + 1;
+        let num_ballots = self.ballots.len();
+
+        // 每张选票当前所在的偏好位置
+        let mut pos = [EXHAUSTED; MAX_STV_BALLOTS];
+        for b in 0..num_ballots {
+            pos[b] = self.next_hopeful(b, 0);
+            if pos[b] == EXHAUSTED {
+                self.exhausted += weight[b];
+            }
+        }
+
+        let mut tallies = [0u128; MAX_STV_CANDIDATES];
+        while self.elected_count < self.seats {
+            // 重新统计仍在竞争的候选人得票，已当选者保持份额
+            for (tally, &status) in tallies.iter_mut().zip(&self.status[..self.num_candidates]) {
+                if status == Status::Hopeful {
+                    *tally = 0;
+                }
+            }
+            for b in 0..num_ballots {
+                if pos[b] != EXHAUSTED {
+                    let c = self.ballots[b][pos[b]];
+                    tallies[c] += weight[b];
+                }
+            }
+
+            if self.elected_count + self.hopeful_count() <= self.seats {
+                self.elect_remaining(quota, &tallies, elected, transcript);
+                break;
+            }
+
+            let best = self
+                .best_hopeful(&tallies)
+                .ok_or(Error::CalculationFailed)?;
+            if tallies[best] >= quota {
+                // 当选并按 盈余/得票 比例转移该候选人的全部选票
+                let tally = tallies[best];
+                let surplus = tally - quota;
+                self.elect(best, elected);
+                let mut transferred = 0u128;
+                for b in 0..num_ballots {
+                    if pos[b] == EXHAUSTED || self.ballots[b][pos[b]] != best {
+                        continue;
+                    }
+                    let w = mul_div(weight[b], surplus, tally).ok_or(Error::CalculationFailed)?;
+                    weight[b] = w;
+                    transferred += w;
+                    pos[b] = self.next_hopeful(b, pos[b] + 1);
+                    if pos[b] == EXHAUSTED {
+                        self.exhausted += w;
+                    }
+                }
+                self.record(
+                    transcript,
+                    StvAction::Elected,
+                    best,
+                    quota,
+                    transferred,
+                    &tallies,
+                );
+                tallies[best] = quota;
+            } else {
+                // 淘汰得票最低者，其选票以当前票值全部转移
+                let worst = self
+                    .worst_hopeful(&tallies)
+                    .ok_or(Error::CalculationFailed)?;
+                self.status[worst] = Status::Excluded;
+                for b in 0..num_ballots {
+                    if pos[b] == EXHAUSTED || self.ballots[b][pos[b]] != worst {
+                        continue;
+                    }
+                    pos[b] = self.next_hopeful(b, pos[b] + 1);
+                    if pos[b] == EXHAUSTED {
+                        self.exhausted += weight[b];
+                    }
+                }
+                self.record(
+                    transcript,
+                    StvAction::Eliminated,
+                    worst,
+                    quota,
+                    tallies[worst],
+                    &tallies,
+                );
+                tallies[worst] = 0;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 按保留系数分配所有选票，返回 (各候选人得票, 耗尽票值)
+    fn meek_distribute(
+        &self,
+        weight: &[u128; MAX_STV_BALLOTS],
+        keep: &[u128; MAX_STV_CANDIDATES],
+    ) -> Result<([u128; MAX_STV_CANDIDATES], u128)> {
+        let mut tallies = [0u128; MAX_STV_CANDIDATES];
+        let mut excess = 0u128;
+        for (b, prefs) in self.ballots.iter().enumerate() {
+            let mut remaining = weight[b];
+            for &c in prefs.iter() {
+                if remaining == 0 {
+                    break;
+                }
+                if self.status[c] == Status::Excluded {
+                    continue;
+                }
+                let give =
+                    mul_div(remaining, keep[c], STV_SCALE).ok_or(Error::CalculationFailed)?;
+                tallies[c] += give;
+                remaining -= give;
+            }
+            excess += remaining;
+        }
+        Ok((tallies, excess))
+    }
+
+    fn run_meek(
+        &mut self,
+        weight: &[u128; MAX_STV_BALLOTS],
+        total: u128,
+        elected: &mut [usize],
+        transcript: &mut [StvRound],
+    ) -> Result<()> {
+        // 保留系数：竞争中为1，淘汰为0，当选者迭代收敛到 份额/得票
+        let mut keep = [STV_SCALE; MAX_STV_CANDIDATES];
+        let seats_plus_one = self.seats as u128 + 1;
+
+        while self.elected_count < self.seats {
+            let mut tallies;
+            let mut quota;
+            let mut iterations = 0usize;
+            loop {
+                let (t, excess) = self.meek_distribute(weight, &keep)?;
+                tallies = t;
+                self.exhausted = excess;
+                quota = (total - excess) / seats_plus_one + 1;
+
+                // 有竞争者达到份额时无需继续收敛
+                let mut reached = false;
+                let mut converged = true;
+                for (&tally, &status) in tallies.iter().zip(&self.status[..self.num_candidates]) {
+                    match status {
+                        Status::Hopeful if tally >= quota => reached = true,
+                        Status::Elected if tally.abs_diff(quota) > MEEK_TOLERANCE => {
+                            converged = false
+                        }
+                        _ => {}
+                    }
+                }
+                iterations += 1;
+                if reached || converged || iterations >= MEEK_MAX_ITERATIONS {
+                    break;
+                }
+
+                // 更新当选者的保留系数：keep = ceil(keep * quota / tally)
+                for c in 0..self.num_candidates {
+                    if self.status[c] == Status::Elected && tallies[c] > 0 {
+                        let k = mul_div_ceil(keep[c], quota, tallies[c])
+                            .ok_or(Error::CalculationFailed)?;
+                        keep[c] = k.min(STV_SCALE);
+                    }
+                }
+            }
+
+            if self.elected_count + self.hopeful_count() <= self.seats {
+                self.elect_remaining(quota, &tallies, elected, transcript);
+                break;
+            }
+
+            let best = self
+                .best_hopeful(&tallies)
+                .ok_or(Error::CalculationFailed)?;
+            if tallies[best] >= quota {
+                // 当选所有达到份额的候选人（得票从高到低）
+                while let Some(c) = self.best_hopeful(&tallies) {
+                    if tallies[c] < quota || self.elected_count >= self.seats {
+                        break;
+                    }
+                    self.elect(c, elected);
+                    let surplus = tallies[c] - quota;
+                    self.record(transcript, StvAction::Elected, c, quota, surplus, &tallies);
+                }
+            } else {
+                let worst = self
+                    .worst_hopeful(&tallies)
+                    .ok_or(Error::CalculationFailed)?;
+                self.status[worst] = Status::Excluded;
+                keep[worst] = 0;
+                self.record(
+                    transcript,
+                    StvAction::Eliminated,
+                    worst,
+                    quota,
+                    tallies[worst],
+                    &tallies,
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -3,6 +3,7 @@ extern crate alloc;
 extern crate std;
 use super::*;
 use alloc::collections::BTreeMap;
+use alloc::vec;
 use alloc::vec::Vec;
 
 #[cfg(feature = "log_tests")]
@@ -38,9 +39,8 @@ fn test_calculate_fair_division_equal_weights_with_map() {
     bids.insert(1usize, 20i128);
 
     // Deterministic order by key
-    let values: Vec<i128> = bids.into_iter().map(|(_k, v)| v).collect();
-    let mut output: Vec<i128> = Vec::with_capacity(values.len());
-    output.resize(values.len(), 0i128);
+    let values: Vec<i128> = bids.into_values().collect();
+    let mut output: Vec<i128> = vec![0; values.len()];
 
     let res = calculate_fair_division_equal_weights(&values, &mut output);
     assert!(res.is_ok());
@@ -81,8 +81,7 @@ fn test_calculate_fair_division_weighted_with_map() {
     // Collect in the same key order
     let values_vec: Vec<i128> = bids.values().cloned().collect();
     let weights_vec: Vec<i128> = weights.values().cloned().collect();
-    let mut output: Vec<i128> = Vec::with_capacity(values_vec.len());
-    output.resize(values_vec.len(), 0i128);
+    let mut output: Vec<i128> = vec![0; values_vec.len()];
 
     let res = calculate_fair_division_weighted(&values_vec, &weights_vec, &mut output);
     assert!(res.is_ok());
@@ -129,7 +128,12 @@ fn test_get_k_dd_rand_num_basic() {
     let group2 = [150u128, 250, 350];
     let group3 = [120u128, 220, 320];
     let group4 = [130u128, 230, 330]; // 添加第4组，使n=4（2的2次幂）
-    let groups = [group1.as_slice(), group2.as_slice(), group3.as_slice(), group4.as_slice()];
+    let groups = [
+        group1.as_slice(),
+        group2.as_slice(),
+        group3.as_slice(),
+        group4.as_slice(),
+    ];
     let mut output = [0usize; 3];
     let res = get_k_dd_rand_num(&groups, 4, 3, &mut output);
     assert!(res.is_ok());
@@ -168,11 +172,16 @@ fn test_get_k_dd_rand_num_with_maps() {
     let group3: Vec<u128> = g3.values().cloned().collect();
     let group4: Vec<u128> = g4.values().cloned().collect();
 
-    let groups: Vec<&[u128]> = [group1.as_slice(), group2.as_slice(), group3.as_slice(), group4.as_slice()].to_vec();
+    let groups: Vec<&[u128]> = [
+        group1.as_slice(),
+        group2.as_slice(),
+        group3.as_slice(),
+        group4.as_slice(),
+    ]
+    .to_vec();
     let n = groups.len();
     let k = group1.len();
-    let mut output: Vec<usize> = Vec::with_capacity(k);
-    output.resize(k, 0usize);
+    let mut output: Vec<usize> = vec![0; k];
 
     let res = get_k_dd_rand_num(&groups, n, k, &mut output);
     assert!(res.is_ok());
@@ -255,8 +264,7 @@ fn test_get_k_dd_rand_num_large_n_branch() {
     let mut i = 0usize;
     while i < n {
         // simple deterministic values per position
-        let mut g: Vec<u128> = Vec::with_capacity(k);
-        g.resize(k, 0);
+        let mut g: Vec<u128> = vec![0; k];
         g[0] = (i as u128) % 997;
         g[1] = ((i as u128) * 7) % 997;
         owned_groups.push(g);
@@ -269,8 +277,7 @@ fn test_get_k_dd_rand_num_large_n_branch() {
         groups.push(owned_groups[j].as_slice());
         j += 1;
     }
-    let mut output: Vec<usize> = Vec::with_capacity(k);
-    output.resize(k, 0usize);
+    let mut output: Vec<usize> = vec![0; k];
     let res = get_k_dd_rand_num(&groups, n, k, &mut output);
     assert!(res.is_ok());
     assert!(output[0] < n && output[1] < n);
@@ -279,3 +286,2560 @@ fn test_get_k_dd_rand_num_large_n_branch() {
     }
     test_log!("k_dd(large_n) n={} k={} output={:?}", n, k, output);
 }
+
+#[test]
+fn test_calculate_stv_election_gregory_with_elimination() {
+    let b0 = [0usize, 1];
+    let b1 = [1usize, 0];
+    let b2 = [2usize, 3];
+    let b3 = [3usize, 2];
+    let b4 = [3usize, 1];
+    let ballots = [
+        b0.as_slice(),
+        b1.as_slice(),
+        b2.as_slice(),
+        b3.as_slice(),
+        b4.as_slice(),
+    ];
+    let weights = [40u128, 10, 20, 15, 15];
+    let mut elected = [0usize; 2];
+    let mut transcript = [StvRound::default(); 4];
+    let rounds = calculate_stv_election(
+        &ballots,
+        &weights,
+        4,
+        2,
+        StvTransfer::Gregory,
+        &mut elected,
+        &mut transcript,
+    )
+    .unwrap();
+    assert_eq!(elected, [0, 3]);
+    assert_eq!(rounds, 4);
+
+    // 第1轮：候选人0以40票当选，盈余约6.67票转给候选人1
+    let quota = 100 * STV_SCALE / 3 + 1;
+    assert_eq!(transcript[0].action, StvAction::Elected);
+    assert_eq!(transcript[0].candidate, 0);
+    assert_eq!(transcript[0].quota, quota);
+    assert_eq!(transcript[0].tallies[0], 40 * STV_SCALE);
+    assert_eq!(transcript[0].transferred, 40 * STV_SCALE - quota);
+    // 第2、3轮依次淘汰候选人1和2
+    assert_eq!(transcript[1].action, StvAction::Eliminated);
+    assert_eq!(transcript[1].candidate, 1);
+    assert_eq!(transcript[2].action, StvAction::Eliminated);
+    assert_eq!(transcript[2].candidate, 2);
+    assert_eq!(transcript[3].candidate, 3);
+    test_log!("stv(gregory) elected={:?} rounds={}", elected, rounds);
+}
+
+#[test]
+fn test_calculate_stv_election_meek_matches_gregory() {
+    let b0 = [0usize, 1, 2];
+    let b1 = [0usize, 2, 1];
+    let b2 = [1usize, 2];
+    let b3 = [2usize, 1];
+    let b4 = [3usize, 2];
+    let ballots = [
+        b0.as_slice(),
+        b1.as_slice(),
+        b2.as_slice(),
+        b3.as_slice(),
+        b4.as_slice(),
+    ];
+    let weights = [30u128, 25, 14, 16, 15];
+
+    let mut gregory = [0usize; 2];
+    let mut meek = [0usize; 2];
+    let mut transcript = [StvRound::default(); 4];
+    calculate_stv_election(
+        &ballots,
+        &weights,
+        4,
+        2,
+        StvTransfer::Gregory,
+        &mut gregory,
+        &mut transcript,
+    )
+    .unwrap();
+    let rounds = calculate_stv_election(
+        &ballots,
+        &weights,
+        4,
+        2,
+        StvTransfer::Meek,
+        &mut meek,
+        &mut transcript,
+    )
+    .unwrap();
+    assert_eq!(gregory, meek);
+    assert_eq!(meek[0], 0);
+
+    // 记录中的票值（含耗尽票）不超过总票值
+    for round in &transcript[..rounds] {
+        let sum: u128 = round.tallies[..4].iter().sum();
+        assert!(sum + round.exhausted <= 100 * STV_SCALE);
+    }
+    test_log!("stv(meek) elected={:?} rounds={}", meek, rounds);
+}
+
+#[test]
+fn test_calculate_stv_election_invalid_ballot() {
+    let b0 = [0usize, 0]; // duplicate preference
+    let ballots = [b0.as_slice()];
+    let weights = [1u128];
+    let mut elected = [0usize; 1];
+    let mut transcript = [StvRound::default(); 2];
+    let res = calculate_stv_election(
+        &ballots,
+        &weights,
+        2,
+        1,
+        StvTransfer::Gregory,
+        &mut elected,
+        &mut transcript,
+    );
+    assert!(matches!(res, Err(Error::InvalidInput)));
+}
+
+#[test]
+fn test_math_mul_div_wide_intermediate() {
+    let a = u128::MAX / 3;
+    assert_eq!(crate::math::mul_div(a, 6, 4), Some(u128::MAX / 2));
+    assert_eq!(
+        crate::math::mul_div(u128::MAX, u128::MAX, u128::MAX),
+        Some(u128::MAX)
+    );
+    assert_eq!(crate::math::mul_div_ceil(10, 10, 3), Some(34));
+    assert_eq!(crate::math::mul_div(u128::MAX, 2, 1), None);
+}
+
+#[test]
+fn test_calculate_apportionment_methods() {
+    let votes = [53_000u128, 24_000, 23_000];
+    let mut seats = [0usize; 3];
+
+    calculate_apportionment(&votes, 7, ApportionmentMethod::DHondt, 0, 0, &mut seats).unwrap();
+    assert_eq!(seats, [4, 2, 1]);
+
+    calculate_apportionment(
+        &votes,
+        7,
+        ApportionmentMethod::SainteLague,
+        0,
+        0,
+        &mut seats,
+    )
+    .unwrap();
+    assert_eq!(seats, [3, 2, 2]);
+
+    calculate_apportionment(&votes, 7, ApportionmentMethod::Hamilton, 0, 0, &mut seats).unwrap();
+    assert_eq!(seats, [4, 2, 1]);
+
+    // Huntington–Hill 先给每个团体一席
+    calculate_apportionment(
+        &votes,
+        3,
+        ApportionmentMethod::HuntingtonHill,
+        0,
+        0,
+        &mut seats,
+    )
+    .unwrap();
+    assert_eq!(seats, [1, 1, 1]);
+    calculate_apportionment(
+        &votes,
+        7,
+        ApportionmentMethod::HuntingtonHill,
+        0,
+        0,
+        &mut seats,
+    )
+    .unwrap();
+    assert_eq!(seats.iter().sum::<usize>(), 7);
+    test_log!("apportionment(hh) votes={:?} seats={:?}", votes, seats);
+}
+
+#[test]
+fn test_calculate_apportionment_threshold_and_tie_break() {
+    // 5% 门槛排除最后一个团体
+    let votes = [500u128, 300, 170, 30];
+    let mut seats = [0usize; 4];
+    calculate_apportionment(
+        &votes,
+        10,
+        ApportionmentMethod::SainteLague,
+        500,
+        0,
+        &mut seats,
+    )
+    .unwrap();
+    assert_eq!(seats[3], 0);
+    assert_eq!(seats.iter().sum::<usize>(), 10);
+
+    // 完全平局时由种子决定，且相同种子结果相同
+    let tied = [100u128, 100, 100];
+    let mut a = [0usize; 3];
+    let mut b = [0usize; 3];
+    calculate_apportionment(&tied, 1, ApportionmentMethod::DHondt, 0, 42, &mut a).unwrap();
+    calculate_apportionment(&tied, 1, ApportionmentMethod::DHondt, 0, 42, &mut b).unwrap();
+    assert_eq!(a, b);
+    assert_eq!(a.iter().sum::<usize>(), 1);
+
+    let mut winners = [false; 3];
+    for seed in 0..32u128 {
+        calculate_apportionment(&tied, 1, ApportionmentMethod::Hamilton, 0, seed, &mut a).unwrap();
+        winners[a.iter().position(|&s| s == 1).unwrap()] = true;
+    }
+    assert!(winners.iter().all(|&w| w));
+
+    // 没有团体达到门槛
+    let res = calculate_apportionment(
+        &votes,
+        10,
+        ApportionmentMethod::DHondt,
+        10_000,
+        0,
+        &mut seats,
+    );
+    assert!(matches!(res, Err(Error::InvalidInput)));
+}
+
+#[test]
+fn test_calculate_participatory_budget_equal_shares_is_proportional() {
+    // 三位投票者支持A1..A3，一位投票者只支持B
+    let a = [0usize, 1, 2];
+    let b = [3usize];
+    let ballots = [a.as_slice(), a.as_slice(), a.as_slice(), b.as_slice()];
+    let weights = [1u128, 1, 1, 1];
+    let costs = [40u128, 40, 40, 30];
+    let mut funded = [false; 4];
+    let mut spent = [0u128; 4];
+
+    let total = calculate_participatory_budget(
+        &costs,
+        120,
+        &ballots,
+        &weights,
+        BudgetingRule::GreedyUtilitarian,
+        &mut funded,
+        &mut spent,
+    )
+    .unwrap();
+    assert_eq!(funded, [true, true, true, false]);
+    assert_eq!(total, 120);
+    assert_eq!(spent[3], 0);
+    assert_eq!(spent.iter().sum::<u128>(), 120);
+
+    let total = calculate_participatory_budget(
+        &costs,
+        120,
+        &ballots,
+        &weights,
+        BudgetingRule::EqualShares,
+        &mut funded,
+        &mut spent,
+    )
+    .unwrap();
+    assert_eq!(funded, [true, true, false, true]);
+    assert_eq!(total, 110);
+    assert_eq!(spent[3], 30);
+    assert_eq!(spent.iter().sum::<u128>(), total);
+    assert!(spent.iter().all(|&s| s <= 30));
+    test_log!("pb(mes) funded={:?} spent={:?}", funded, spent);
+}
+
+#[test]
+fn test_calculate_participatory_budget_completion_and_phragmen() {
+    let all = [0usize, 1, 2];
+    let a = [0usize, 1];
+    let b = [2usize, 0];
+    let ballots = [all.as_slice(), a.as_slice(), a.as_slice(), b.as_slice()];
+    let weights = [1u128, 1, 1, 1];
+    let costs = [30u128, 60, 50];
+    let mut funded = [false; 3];
+    let mut spent = [0u128; 4];
+
+    // 等份额法只资助项目0，补全后再加入项目1
+    let total = calculate_participatory_budget(
+        &costs,
+        100,
+        &ballots,
+        &weights,
+        BudgetingRule::EqualShares,
+        &mut funded,
+        &mut spent,
+    )
+    .unwrap();
+    assert_eq!(funded, [true, false, false]);
+    assert_eq!(total, 30);
+
+    let total = calculate_participatory_budget(
+        &costs,
+        100,
+        &ballots,
+        &weights,
+        BudgetingRule::EqualSharesWithCompletion,
+        &mut funded,
+        &mut spent,
+    )
+    .unwrap();
+    assert_eq!(funded, [true, true, false]);
+    assert_eq!(total, 90);
+    assert_eq!(spent.iter().sum::<u128>(), 90);
+
+    let total = calculate_participatory_budget(
+        &costs,
+        100,
+        &ballots,
+        &weights,
+        BudgetingRule::Phragmen,
+        &mut funded,
+        &mut spent,
+    )
+    .unwrap();
+    assert_eq!(funded, [true, true, false]);
+    assert_eq!(spent.iter().sum::<u128>(), total);
+    test_log!("pb(phragmen) funded={:?} spent={:?}", funded, spent);
+}
+
+#[test]
+fn test_calculate_power_index_exact() {
+    let weights = [50u128, 49, 1];
+    let mut scratch = vec![0u128; power_index_scratch_len(3, 51)];
+    let mut output = [0u128; 3];
+
+    calculate_power_index(&weights, 51, PowerIndex::Banzhaf, &mut scratch, &mut output).unwrap();
+    assert_eq!(output, [600_000_000, 200_000_000, 200_000_000]);
+
+    // 配额5时成员0是所有获胜联盟的必要成员；成员3为无权成员
+    let weights = [3u128, 2, 2, 0];
+    let mut scratch = vec![0u128; power_index_scratch_len(4, 5)];
+    let mut output = [0u128; 4];
+    calculate_power_index(
+        &weights,
+        5,
+        PowerIndex::ShapleyShubik,
+        &mut scratch,
+        &mut output,
+    )
+    .unwrap();
+    assert_eq!(output[3], 0);
+    assert_eq!(output[1], output[2]);
+    assert!(output[0] > output[1]);
+    let sum: u128 = output.iter().sum();
+    assert!(POWER_INDEX_SCALE - sum < 10);
+
+    // 缓冲区不足
+    let mut small = [0u128; 4];
+    let res = calculate_power_index(&weights, 5, PowerIndex::Banzhaf, &mut small, &mut output);
+    assert!(matches!(res, Err(Error::InvalidInput)));
+    test_log!("power_index(ss) weights={:?} output={:?}", weights, output);
+}
+
+#[test]
+fn test_estimate_power_index_close_to_exact() {
+    let weights = [4u128, 3, 2, 1];
+    let quota = 6u128;
+    let mut scratch = vec![0u128; power_index_scratch_len(4, quota)];
+    let mut exact = [0u128; 4];
+    let mut estimate = [0u128; 4];
+
+    for index in [PowerIndex::Banzhaf, PowerIndex::ShapleyShubik] {
+        calculate_power_index(&weights, quota, index, &mut scratch, &mut exact).unwrap();
+        estimate_power_index(&weights, quota, index, 0xDEAD_BEEF, 20_000, &mut estimate).unwrap();
+        for i in 0..4 {
+            // 误差不超过0.02
+            assert!(exact[i].abs_diff(estimate[i]) < POWER_INDEX_SCALE / 50);
+        }
+        test_log!(
+            "power_index {:?} exact={:?} mc={:?}",
+            index,
+            exact,
+            estimate
+        );
+    }
+}
+
+#[test]
+fn test_decentralization_metrics_extremes() {
+    let equal = [25u128, 25, 25, 25];
+    let monopoly = [0u128, 100, 0, 0];
+    let mut scratch = [0u128; 4];
+
+    assert_eq!(calculate_gini_coefficient(&equal, &mut scratch).unwrap(), 0);
+    assert_eq!(calculate_hhi(&equal).unwrap(), METRICS_SCALE / 4);
+    assert_eq!(calculate_theil_index(&equal).unwrap(), 0);
+    assert_eq!(
+        calculate_nakamoto_coefficient(&equal, 1, 3, &mut scratch).unwrap(),
+        2
+    );
+    assert_eq!(
+        calculate_nakamoto_coefficient(&equal, 1, 2, &mut scratch).unwrap(),
+        3
+    );
+
+    // 单一实体控制全部权力：G = (n-1)/n，HHI = 1，T = ln(n)
+    assert_eq!(
+        calculate_gini_coefficient(&monopoly, &mut scratch).unwrap(),
+        750_000_000
+    );
+    assert_eq!(calculate_hhi(&monopoly).unwrap(), METRICS_SCALE);
+    let theil = calculate_theil_index(&monopoly).unwrap();
+    assert!(theil.abs_diff(1_386_294_361) <= 1);
+    assert_eq!(
+        calculate_nakamoto_coefficient(&monopoly, 1, 2, &mut scratch).unwrap(),
+        1
+    );
+    test_log!("metrics(monopoly) theil={}", theil);
+}
+
+#[test]
+fn test_decentralization_metrics_invalid_input() {
+    let zero = [0u128, 0];
+    let mut scratch = [0u128; 2];
+    assert!(matches!(calculate_hhi(&zero), Err(Error::InvalidInput)));
+    assert!(matches!(
+        calculate_nakamoto_coefficient(&[1u128, 2], 1, 1, &mut scratch),
+        Err(Error::InvalidInput)
+    ));
+    // 缓冲区过短
+    let mut short = [0u128; 1];
+    assert!(matches!(
+        calculate_gini_coefficient(&[1u128, 2], &mut short),
+        Err(Error::InvalidInput)
+    ));
+
+    // 一般分布：指标都在合理范围内
+    let powers = [1u128, 2, 3, 4, 90];
+    let mut scratch = [0u128; 5];
+    let gini = calculate_gini_coefficient(&powers, &mut scratch).unwrap();
+    assert_eq!(gini, 720_000_000);
+    let hhi = calculate_hhi(&powers).unwrap();
+    assert_eq!(hhi, 813_000_000);
+}
+
+#[test]
+fn test_get_one_dd_3d_rand_num_uses_k() {
+    let values = [0xFFu128, 0x0F, 0x30, 0x01];
+    let mut result = 0u128;
+    get_one_dd_3d_rand_num(&values, 4, 16, &mut result).unwrap();
+    assert_eq!(result, (0xFF ^ 0x0F ^ 0x30 ^ 0x01) % 16);
+    assert!(result < 16);
+}
+
+#[test]
+fn test_get_dd_lottery_draw_formats() {
+    let values = [11u128, 22, 33, 44];
+    let mut digits = [0u8; 3];
+    get_dd_lottery_draw(&values, 4, LotteryFormat::FUCAI_3D, &mut digits).unwrap();
+    assert!(digits.iter().all(|&d| d <= 9));
+
+    // 相同输入得到相同开奖号码
+    let mut again = [0u8; 3];
+    get_dd_lottery_draw(&values, 4, LotteryFormat::FUCAI_3D, &mut again).unwrap();
+    assert_eq!(digits, again);
+
+    // 开奖号码取自与dd_shuffle等函数相同的DdRng随机数流
+    let mut rng = DdRng::from_contributions(&values, 4).unwrap();
+    let number = rng.gen_below(1000);
+    let expected = [
+        (number / 100) as u8,
+        (number / 10 % 10) as u8,
+        (number % 10) as u8,
+    ];
+    assert_eq!(digits, expected);
+
+    let mut pick5 = [0u8; 5];
+    get_dd_lottery_draw(&values, 4, LotteryFormat::PICK_5, &mut pick5).unwrap();
+    assert!(pick5.iter().all(|&d| d <= 9));
+
+    let mut lotto = [0u8; 6];
+    get_dd_lottery_draw(&values, 4, LotteryFormat::SIX_OF_49, &mut lotto).unwrap();
+    assert!(lotto.iter().all(|&b| (1..=49).contains(&b)));
+    assert!(lotto.windows(2).all(|w| w[0] < w[1]));
+
+    let mut rng = DdRng::from_contributions(&values, 4).unwrap();
+    let mut balls: Vec<u8> = (1..=49).collect();
+    for i in 0..6 {
+        let j = i + rng.gen_below((49 - i) as u128) as usize;
+        balls.swap(i, j);
+    }
+    balls.truncate(6);
+    balls.sort_unstable();
+    assert_eq!(&lotto[..], &balls[..]);
+
+    // 输出长度与玩法不符
+    let res = get_dd_lottery_draw(&values, 4, LotteryFormat::FUCAI_3D, &mut lotto);
+    assert!(matches!(res, Err(Error::InvalidInput)));
+    test_log!("lottery 3d={:?} pick5={:?} 6/49={:?}", digits, pick5, lotto);
+}
+
+#[test]
+fn test_get_dd_lottery_draw_digit_frequencies() {
+    // 每位数字在大量开奖中大致均匀分布
+    let mut counts = [0usize; 10];
+    for i in 0..2_000u128 {
+        let values = [i, i.wrapping_mul(0x9E37_79B9), 7, 0];
+        let mut digits = [0u8; 3];
+        get_dd_lottery_draw(&values, 4, LotteryFormat::FUCAI_3D, &mut digits).unwrap();
+        for d in digits {
+            counts[d as usize] += 1;
+        }
+    }
+    for &c in &counts {
+        assert!((450..=750).contains(&c), "counts={:?}", counts);
+    }
+}
+
+#[test]
+fn test_match_lottery_tickets() {
+    assert_eq!(
+        match_3d_ticket(&[1, 1, 2], &[2, 1, 1], Play3d::Box).unwrap(),
+        Prize3d::Group3
+    );
+    assert_eq!(
+        match_3d_ticket(&[4, 5, 6], &[6, 4, 5], Play3d::Box).unwrap(),
+        Prize3d::Group6
+    );
+    // 豹子号组选不中奖，直选中奖
+    assert_eq!(
+        match_3d_ticket(&[7, 7, 7], &[7, 7, 7], Play3d::Box).unwrap(),
+        Prize3d::NoPrize
+    );
+    assert_eq!(
+        match_3d_ticket(&[7, 7, 7], &[7, 7, 7], Play3d::Straight).unwrap(),
+        Prize3d::Straight
+    );
+    assert!(matches!(
+        match_3d_ticket(&[1, 2, 10], &[1, 2, 3], Play3d::Straight),
+        Err(Error::InvalidInput)
+    ));
+
+    let draw = [3u8, 11, 19, 27, 35, 49];
+    let ticket = [1u8, 3, 19, 20, 35, 48];
+    assert_eq!(
+        count_lottery_matches(&ticket, &draw, LotteryFormat::SIX_OF_49).unwrap(),
+        3
+    );
+    assert_eq!(
+        count_lottery_matches(&[1, 2, 3, 4, 5], &[1, 2, 0, 4, 0], LotteryFormat::PICK_5).unwrap(),
+        3
+    );
+
+    // 乐透型彩票的号码重复或超出号码池时被拒绝，不会重复计数
+    assert_eq!(
+        count_lottery_matches(&[3, 3, 3, 3, 3, 3], &draw, LotteryFormat::SIX_OF_49),
+        Err(Error::InvalidInput)
+    );
+    assert_eq!(
+        count_lottery_matches(&[0, 3, 19, 20, 35, 48], &draw, LotteryFormat::SIX_OF_49),
+        Err(Error::InvalidInput)
+    );
+    assert_eq!(
+        count_lottery_matches(&[3, 11, 19, 27, 35, 50], &draw, LotteryFormat::SIX_OF_49),
+        Err(Error::InvalidInput)
+    );
+    // 号码顺序不限
+    assert_eq!(
+        count_lottery_matches(&[49, 35, 27, 19, 11, 3], &draw, LotteryFormat::SIX_OF_49).unwrap(),
+        6
+    );
+}
+
+#[test]
+fn test_settle_lottery_pari_mutuel_and_jackpot() {
+    // 6/49：中6个为头奖，中5个奖池分成，中3个固定奖金
+    let tiers = [
+        PrizeTier {
+            condition: TierMatch::Matches(6),
+            payout: TierPayout::Jackpot { share_bps: 5_000 },
+        },
+        PrizeTier {
+            condition: TierMatch::Matches(5),
+            payout: TierPayout::PariMutuel { share_bps: 3_000 },
+        },
+        PrizeTier {
+            condition: TierMatch::Matches(3),
+            payout: TierPayout::Fixed(10),
+        },
+    ];
+    let draw = [1u8, 2, 3, 4, 5, 6];
+    let t5a = [1u8, 2, 3, 4, 5, 40];
+    let t5b = [1u8, 2, 3, 4, 6, 41];
+    let t5c = [1u8, 2, 3, 5, 6, 42];
+    let t3 = [1u8, 2, 3, 30, 31, 32];
+    let t0 = [10u8, 11, 12, 13, 14, 15];
+    let tickets = [
+        LotteryTicket {
+            numbers: &t5a,
+            play: Play3d::Straight,
+        },
+        LotteryTicket {
+            numbers: &t5b,
+            play: Play3d::Straight,
+        },
+        LotteryTicket {
+            numbers: &t5c,
+            play: Play3d::Straight,
+        },
+        LotteryTicket {
+            numbers: &t3,
+            play: Play3d::Straight,
+        },
+        LotteryTicket {
+            numbers: &t0,
+            play: Play3d::Straight,
+        },
+    ];
+    let mut payouts = [0u128; 5];
+    let mut ticket_tiers = [None; 5];
+    let report = settle_lottery(
+        &tickets,
+        &draw,
+        LotteryFormat::SIX_OF_49,
+        &tiers,
+        1_010,
+        500,
+        &mut payouts,
+        &mut ticket_tiers,
+    )
+    .unwrap();
+
+    assert_eq!(ticket_tiers, [Some(1), Some(1), Some(1), Some(2), None]);
+    // 剩余奖池1000：分成奖级300由3人平分，头奖500+500无人中出
+    assert_eq!(payouts, [100, 100, 100, 10, 0]);
+    assert_eq!(report.winners[..3], [0, 3, 1]);
+    assert_eq!(report.total_paid, 310);
+    assert_eq!(report.rollover, 1_200);
+    assert_eq!(report.total_paid + report.rollover, 1_010 + 500);
+    assert!(!report.fixed_scaled);
+    test_log!("settle payouts={:?} report={:?}", payouts, report);
+}
+
+#[test]
+fn test_settle_lottery_remainder_and_scaled_fixed() {
+    let tiers = [PrizeTier {
+        condition: TierMatch::Fucai3d(Prize3d::Straight),
+        payout: TierPayout::Jackpot { share_bps: 10_000 },
+    }];
+    let tickets = [
+        LotteryTicket {
+            numbers: &[5, 5, 5],
+            play: Play3d::Straight,
+        },
+        LotteryTicket {
+            numbers: &[5, 5, 5],
+            play: Play3d::Straight,
+        },
+        LotteryTicket {
+            numbers: &[5, 5, 5],
+            play: Play3d::Straight,
+        },
+    ];
+    let mut payouts = [0u128; 3];
+    let mut ticket_tiers = [None; 3];
+    let report = settle_lottery(
+        &tickets,
+        &[5, 5, 5],
+        LotteryFormat::FUCAI_3D,
+        &tiers,
+        100,
+        0,
+        &mut payouts,
+        &mut ticket_tiers,
+    )
+    .unwrap();
+    // 100 / 3 = 33，余数1滚存
+    assert_eq!(payouts, [33, 33, 33]);
+    assert_eq!(report.rollover, 1);
+
+    // 奖池不足时固定奖金按比例缩减
+    let tiers = [PrizeTier {
+        condition: TierMatch::Fucai3d(Prize3d::Straight),
+        payout: TierPayout::Fixed(1_040),
+    }];
+    let report = settle_lottery(
+        &tickets,
+        &[5, 5, 5],
+        LotteryFormat::FUCAI_3D,
+        &tiers,
+        1_000,
+        0,
+        &mut payouts,
+        &mut ticket_tiers,
+    )
+    .unwrap();
+    assert!(report.fixed_scaled);
+    assert_eq!(payouts, [333, 333, 333]);
+    assert_eq!(report.rollover, 1);
+}
+
+#[test]
+fn test_settle_lottery_rejects_multiple_jackpots() {
+    // 两个头奖奖级会把同一笔上期滚存各计入一次
+    let tiers = [
+        PrizeTier {
+            condition: TierMatch::Fucai3d(Prize3d::Straight),
+            payout: TierPayout::Jackpot { share_bps: 5_000 },
+        },
+        PrizeTier {
+            condition: TierMatch::Fucai3d(Prize3d::Group6),
+            payout: TierPayout::Jackpot { share_bps: 5_000 },
+        },
+    ];
+    let tickets = [
+        LotteryTicket {
+            numbers: &[1, 2, 3],
+            play: Play3d::Straight,
+        },
+        LotteryTicket {
+            numbers: &[3, 2, 1],
+            play: Play3d::Box,
+        },
+    ];
+    let mut payouts = [0u128; 2];
+    let mut ticket_tiers = [None; 2];
+    let res = settle_lottery(
+        &tickets,
+        &[1, 2, 3],
+        LotteryFormat::FUCAI_3D,
+        &tiers,
+        1_000,
+        1_000,
+        &mut payouts,
+        &mut ticket_tiers,
+    );
+    assert_eq!(res, Err(Error::InvalidInput));
+
+    // 单个头奖奖级领取全部滚存
+    let report = settle_lottery(
+        &tickets,
+        &[1, 2, 3],
+        LotteryFormat::FUCAI_3D,
+        &tiers[..1],
+        1_000,
+        1_000,
+        &mut payouts,
+        &mut ticket_tiers,
+    )
+    .unwrap();
+    assert_eq!(payouts, [1_500, 0]);
+    assert_eq!(report.total_paid + report.rollover, 2_000);
+}
+
+#[test]
+fn test_dd_shuffle_is_deterministic_permutation() {
+    let values = [7u128, 11, 13, 17];
+    // 超过get_k_dd_rand_num的k上限
+    let n_items = 5_000usize;
+    let mut a: Vec<usize> = (0..n_items).collect();
+    let mut b: Vec<usize> = (0..n_items).collect();
+    dd_shuffle(&values, 4, &mut a).unwrap();
+    dd_shuffle(&values, 4, &mut b).unwrap();
+    assert_eq!(a, b);
+    assert_ne!(a, (0..n_items).collect::<Vec<_>>());
+
+    let mut sorted = a.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, (0..n_items).collect::<Vec<_>>());
+
+    // 不同的贡献值得到不同的排列
+    let other = [7u128, 11, 13, 18];
+    let mut c: Vec<usize> = (0..n_items).collect();
+    dd_shuffle(&other, 4, &mut c).unwrap();
+    assert_ne!(a, c);
+
+    // 与select_k_eligible等函数共用同一个DdRng随机数流
+    let mut rng = DdRng::from_contributions(&values, 4).unwrap();
+    let mut expected: Vec<usize> = (0..n_items).collect();
+    for i in (1..n_items).rev() {
+        expected.swap(i, rng.gen_below(i as u128 + 1) as usize);
+    }
+    assert_eq!(a, expected);
+
+    // 空切片和单元素切片
+    let mut empty: [u8; 0] = [];
+    dd_shuffle(&values, 4, &mut empty).unwrap();
+    // 参与者数量不是2的n次幂
+    let res = dd_shuffle(&values[..3], 3, &mut a);
+    assert!(matches!(res, Err(Error::InvalidInput)));
+}
+
+#[test]
+fn test_dd_shuffle_position_frequencies() {
+    // 3个元素的6种排列出现频率大致相等
+    let mut counts = BTreeMap::new();
+    for i in 0..6_000u128 {
+        let values = [i, i * 31 + 5];
+        let mut items = [0u8, 1, 2];
+        dd_shuffle(&values, 2, &mut items).unwrap();
+        *counts.entry(items).or_insert(0usize) += 1;
+    }
+    assert_eq!(counts.len(), 6);
+    for &c in counts.values() {
+        assert!((850..=1150).contains(&c), "counts={:?}", counts);
+    }
+}
+
+#[test]
+fn test_dd_rng_matches_chacha20_keystream() {
+    // RFC 8439 附录A.1 测试向量#1和#2：全零密钥、全零nonce，块计数器0和1
+    let expected = [
+        "76b8e0ada0f13d90405d6ae55386bd28bdd219b8a08ded1aa836efcc8b770dc7",
+        "da41597c5157488d7724e03fb8d84a376a43b8f41518a11cc387b669b2ee6586",
+        "9f07e7be5551387a98ba977c732d080dcb0f29a048e3656912c6533e32ee7aed",
+        "29b721769ce64e43d57133b074d839d531ed1f28510afb45ace10a1f4b794d6f",
+    ];
+    let mut rng = DdRng::new(0);
+    let mut stream = [0u8; 128];
+    rng.fill_bytes(&mut stream);
+    for (chunk, hex) in stream.chunks(32).zip(expected) {
+        let mut s = alloc::string::String::new();
+        for b in chunk {
+            s.push_str(&alloc::format!("{:02x}", b));
+        }
+        assert_eq!(s, hex);
+    }
+}
+
+#[test]
+fn test_dd_rng_from_contributions() {
+    let values = [100u128, 200, 300, 400];
+    let mut seed = 0u128;
+    get_one_dd_rand_num(&values, 4, &mut seed).unwrap();
+    let mut a = DdRng::from_contributions(&values, 4).unwrap();
+    let mut b = DdRng::new(seed);
+    for _ in 0..100 {
+        assert_eq!(a.next_u64(), b.next_u64());
+    }
+    assert!(DdRng::from_contributions(&values[..3], 3).is_err());
+
+    // 一个种子驱动任意多次无偏抽取
+    let mut counts = [0usize; 6];
+    for _ in 0..60_000 {
+        counts[a.gen_below(6) as usize] += 1;
+    }
+    for &c in &counts {
+        assert!((9_500..=10_500).contains(&c), "counts={:?}", counts);
+    }
+    assert_eq!(a.gen_below(0), 0);
+    assert_eq!(a.gen_below(1), 0);
+}
+
+#[cfg(feature = "rand_core")]
+#[test]
+fn test_dd_rng_rand_core() {
+    use rand_core::{RngCore, SeedableRng};
+
+    let mut key = [0u8; 32];
+    key[0] = 7;
+    let mut via_trait = <DdRng as SeedableRng>::from_seed(key);
+    let mut inherent = DdRng::from_key(key);
+    assert_eq!(RngCore::next_u64(&mut via_trait), inherent.next_u64());
+    assert_eq!(RngCore::next_u32(&mut via_trait), inherent.next_u32());
+}
+
+#[test]
+fn test_get_k_dd_rand_num_large_unique_and_clean() {
+    let values = [11u128, 22, 33, 44];
+
+    // k = n：得到完整排列
+    let n = 100_000u64;
+    let mut bitset = vec![0u64; dd_bitset_len(n)];
+    let mut output = vec![0u64; n as usize];
+    get_k_dd_rand_num_large(&values, 4, n, &mut bitset, &mut output).unwrap();
+    let mut sorted = output.clone();
+    sorted.sort_unstable();
+    assert!(sorted.iter().enumerate().all(|(i, &x)| x == i as u64));
+    assert!(bitset.iter().all(|&w| w == 0));
+
+    // 超过旧路径的限制：n > 100,000 且不是2的n次幂，k > 1,000
+    let n = (1u64 << 26) + 12_345;
+    let mut bitset = vec![0u64; dd_bitset_len(n)];
+    let mut a = vec![0u64; 20_000];
+    get_k_dd_rand_num_large(&values, 4, n, &mut bitset, &mut a).unwrap();
+    let mut b = vec![0u64; 20_000];
+    get_k_dd_rand_num_large(&values, 4, n, &mut bitset, &mut b).unwrap();
+    assert_eq!(a, b);
+    let mut sorted = a.clone();
+    sorted.sort_unstable();
+    sorted.dedup();
+    assert_eq!(sorted.len(), a.len());
+    assert!(a.iter().all(|&x| x < n));
+    test_log!("large sortition first={:?}", &a[..5]);
+}
+
+#[test]
+fn test_get_k_dd_rand_num_large_invalid_and_uniform() {
+    let values = [1u128, 2];
+    let mut output = [0u64; 4];
+
+    let mut short = [0u64; 1];
+    let res = get_k_dd_rand_num_large(&values, 2, 200, &mut short, &mut output);
+    assert!(matches!(res, Err(Error::InvalidInput)));
+
+    let mut bitset = [0u64; 1];
+    let res = get_k_dd_rand_num_large(&values, 2, 3, &mut bitset, &mut output);
+    assert!(matches!(res, Err(Error::InvalidInput)));
+
+    // 调用前未清零的位图被拒绝且保持不变
+    let mut dirty = [u64::MAX; 1];
+    let res = get_k_dd_rand_num_large(&values, 2, 64, &mut dirty, &mut output);
+    assert!(matches!(res, Err(Error::InvalidInput)));
+    assert_eq!(dirty, [u64::MAX]);
+
+    // 超出已用前缀的字不影响抽样
+    let n = 1_000_000u64;
+    let mut stale = vec![0u64; dd_bitset_len(n) + 1];
+    let mut one = [0u64; 1];
+    stale[dd_bitset_len(n)] = u64::MAX;
+    get_k_dd_rand_num_large(&values, 2, n, &mut stale, &mut one).unwrap();
+
+    // 每个位置的取值大致均匀
+    let mut counts = [0usize; 8];
+    let mut bitset = [0u64; 1];
+    for i in 0..8_000u128 {
+        let mut out = [0u64; 2];
+        get_k_dd_rand_num_large(&[i, 0xabcdef], 2, 8, &mut bitset, &mut out).unwrap();
+        counts[out[1] as usize] += 1;
+    }
+    for &c in &counts {
+        assert!((850..=1150).contains(&c), "counts={:?}", counts);
+    }
+}
+
+#[test]
+fn test_select_k_eligible_inclusion_and_exclusion() {
+    let values = [3u128, 1, 4, 1, 5, 9, 2, 6];
+    let eligible = [7usize, 2, 5];
+    let mut scratch = [0usize; 8];
+    let mut output = [0usize; 3];
+    select_k_eligible(
+        &values,
+        8,
+        Eligibility::Only(&eligible),
+        &mut scratch,
+        &mut output,
+    )
+    .unwrap();
+    let mut sorted = output;
+    sorted.sort_unstable();
+    assert_eq!(sorted, [2, 5, 7]);
+
+    // 列表顺序不影响结果
+    let mut again = [0usize; 3];
+    select_k_eligible(
+        &values,
+        8,
+        Eligibility::Only(&[5, 7, 2]),
+        &mut scratch,
+        &mut again,
+    )
+    .unwrap();
+    assert_eq!(output, again);
+
+    let excluded = [0usize, 1, 2, 3, 4];
+    let mut output = [0usize; 3];
+    select_k_eligible(
+        &values,
+        8,
+        Eligibility::Except(&excluded),
+        &mut scratch,
+        &mut output,
+    )
+    .unwrap();
+    let mut sorted = output;
+    sorted.sort_unstable();
+    assert_eq!(sorted, [5, 6, 7]);
+
+    // 资格集合不足、重复或越界
+    let mut output = [0usize; 4];
+    let res = select_k_eligible(
+        &values,
+        8,
+        Eligibility::Only(&eligible),
+        &mut scratch,
+        &mut output,
+    );
+    assert!(matches!(res, Err(Error::NotEnoughParticipants)));
+    let mut output = [0usize; 1];
+    let res = select_k_eligible(
+        &values,
+        8,
+        Eligibility::Only(&[1, 1]),
+        &mut scratch,
+        &mut output,
+    );
+    assert!(matches!(res, Err(Error::InvalidInput)));
+    let res = select_k_eligible(
+        &values,
+        8,
+        Eligibility::Except(&[8]),
+        &mut scratch,
+        &mut output,
+    );
+    assert!(matches!(res, Err(Error::InvalidInput)));
+    let mut tiny = [0usize; 2];
+    let res = select_k_eligible(&values, 8, Eligibility::All, &mut tiny, &mut output);
+    assert!(matches!(res, Err(Error::InvalidInput)));
+}
+
+#[test]
+fn test_select_k_eligible_uniform_over_small_subset() {
+    // n很大而资格集合很小：缓冲区只需资格集合大小
+    let n = 1usize << 16;
+    let eligible = [10usize, 20_000, 65_535, 300];
+    let mut counts = BTreeMap::new();
+    let mut values = vec![0u128; n];
+    for round in 0..4_000u128 {
+        values[0] = round;
+        let mut scratch = [0usize; 4];
+        let mut output = [0usize; 1];
+        select_k_eligible(
+            &values,
+            n,
+            Eligibility::Only(&eligible),
+            &mut scratch,
+            &mut output,
+        )
+        .unwrap();
+        *counts.entry(output[0]).or_insert(0usize) += 1;
+    }
+    assert_eq!(counts.len(), 4);
+    for &c in counts.values() {
+        assert!((850..=1150).contains(&c), "counts={:?}", counts);
+    }
+}
+
+#[test]
+fn test_select_stratified_quotas() {
+    let n = 64usize;
+    let values: Vec<u128> = (0..n as u128).map(|i| i * 7919 + 1).collect();
+    // 4个类别，类别大小为 32, 16, 8, 8
+    let categories: Vec<usize> = (0..n)
+        .map(|i| match i % 8 {
+            0..=3 => 0,
+            4 | 5 => 1,
+            6 => 2,
+            _ => 3,
+        })
+        .collect();
+    let seats = [5usize, 3, 8, 0];
+    let mut scratch = vec![0usize; n];
+    let mut output = [0usize; 16];
+    select_stratified(&values, n, &categories, &seats, &mut scratch, &mut output).unwrap();
+
+    let mut pos = 0;
+    for (c, &s) in seats.iter().enumerate() {
+        assert!(output[pos..pos + s].iter().all(|&i| categories[i] == c));
+        pos += s;
+    }
+    let mut sorted = output.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    assert_eq!(sorted.len(), 16);
+    test_log!("stratified output={:?}", output);
+
+    let mut again = [0usize; 16];
+    select_stratified(&values, n, &categories, &seats, &mut scratch, &mut again).unwrap();
+    assert_eq!(output, again);
+}
+
+#[test]
+fn test_select_stratified_errors() {
+    let values = [1u128, 2, 3, 4];
+    let categories = [0usize, 1, 1, 2];
+    let mut scratch = [0usize; 4];
+
+    // 类别1只有2名成员却需要3个席位
+    let mut output = [0usize; 4];
+    let res = select_stratified(
+        &values,
+        4,
+        &categories,
+        &[1, 3, 0],
+        &mut scratch,
+        &mut output,
+    );
+    assert_eq!(res, Err(Error::StratumTooSmall { category: 1 }));
+
+    // 类别编号越界、席位总数与输出长度不匹配
+    let mut output = [0usize; 2];
+    let res = select_stratified(&values, 4, &categories, &[1, 1], &mut scratch, &mut output);
+    assert_eq!(res, Err(Error::InvalidInput));
+    let res = select_stratified(
+        &values,
+        4,
+        &categories,
+        &[1, 0, 0],
+        &mut scratch,
+        &mut output,
+    );
+    assert_eq!(res, Err(Error::InvalidInput));
+}
+
+#[test]
+fn test_select_committee_epoch_exclude_and_chain() {
+    let n = 16usize;
+    let mut history: Vec<Vec<usize>> = Vec::new();
+    let mut seeds = Vec::new();
+    let mut seed = 0u128;
+    for epoch in 0..6u128 {
+        let values: Vec<u128> = (0..n as u128).map(|i| i * 1_000 + epoch).collect();
+        let past: Vec<&[usize]> = history.iter().map(|c| c.as_slice()).collect();
+        let mut scratch = vec![0usize; n];
+        let mut committee = vec![0usize; 5];
+        seed = select_committee_epoch(
+            &values,
+            n,
+            seed,
+            &past,
+            2,
+            CooldownPolicy::Exclude,
+            &mut scratch,
+            &mut committee,
+        )
+        .unwrap();
+        for prev in history.iter().rev().take(2) {
+            assert!(committee.iter().all(|m| !prev.contains(m)));
+        }
+        seeds.push(seed);
+        history.push(committee);
+    }
+    test_log!("rotation history={:?}", history);
+
+    // 种子依赖上一纪元：相同的贡献值在不同的链位置得到不同的种子
+    let values: Vec<u128> = (0..n as u128).map(|i| i * 1_000 + 5).collect();
+    let mut scratch = vec![0usize; n];
+    let mut committee = [0usize; 1];
+    let replay = select_committee_epoch(
+        &values,
+        n,
+        seeds[4],
+        &[],
+        0,
+        CooldownPolicy::Exclude,
+        &mut scratch,
+        &mut committee,
+    )
+    .unwrap();
+    assert_eq!(replay, seeds[5]);
+    let other = select_committee_epoch(
+        &values,
+        n,
+        seeds[3],
+        &[],
+        0,
+        CooldownPolicy::Exclude,
+        &mut scratch,
+        &mut committee,
+    )
+    .unwrap();
+    assert_ne!(other, seeds[5]);
+
+    // 冷却期成员过多时无法组成委员会
+    let all: Vec<usize> = (0..n).collect();
+    let mut committee = [0usize; 1];
+    let res = select_committee_epoch(
+        &values,
+        n,
+        0,
+        &[&all],
+        1,
+        CooldownPolicy::Exclude,
+        &mut scratch,
+        &mut committee,
+    );
+    assert!(matches!(res, Err(Error::NotEnoughParticipants)));
+}
+
+#[test]
+fn test_select_committee_epoch_down_weight() {
+    let n = 4usize;
+    let recent = [0usize, 1];
+    let mut cooled = 0usize;
+    let trials = 4_000u128;
+    for t in 0..trials {
+        let values = [t, 7, 11, 13];
+        let mut scratch = [0usize; 4];
+        let mut committee = [0usize; 1];
+        select_committee_epoch(
+            &values,
+            n,
+            0,
+            &[&recent],
+            1,
+            CooldownPolicy::DownWeight { weight_bps: 2_500 },
+            &mut scratch,
+            &mut committee,
+        )
+        .unwrap();
+        if recent.contains(&committee[0]) {
+            cooled += 1;
+        }
+    }
+    // 冷却期成员的总权重为 2 × 0.25 / (2 × 0.25 + 2) = 20%
+    assert!((650..=950).contains(&cooled), "cooled={}", cooled);
+
+    // 权重为0等同于排除，且不会重复选择
+    let values = [1u128, 2, 3, 4];
+    let mut scratch = [0usize; 4];
+    let mut committee = [0usize; 2];
+    select_committee_epoch(
+        &values,
+        n,
+        0,
+        &[&recent],
+        1,
+        CooldownPolicy::DownWeight { weight_bps: 0 },
+        &mut scratch,
+        &mut committee,
+    )
+    .unwrap();
+    committee.sort_unstable();
+    assert_eq!(committee, [2, 3]);
+    let mut committee = [0usize; 3];
+    let res = select_committee_epoch(
+        &values,
+        n,
+        0,
+        &[&recent],
+        1,
+        CooldownPolicy::DownWeight { weight_bps: 0 },
+        &mut scratch,
+        &mut committee,
+    );
+    assert!(matches!(res, Err(Error::NotEnoughParticipants)));
+}
+
+#[test]
+fn test_beacon_chain_replay() {
+    let genesis = BeaconState::genesis(0xfeed_beef, 1_000);
+    let contributions = [[1u128, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]];
+
+    for combiner in [BeaconCombiner::Xor, BeaconCombiner::ChaCha20] {
+        let mut state = genesis;
+        let mut outputs = [0u128; 3];
+        for (i, values) in contributions.iter().enumerate() {
+            state = next_beacon_round(&state, 1_000 + 30 * (i as u64 + 1), values, 4, combiner)
+                .unwrap();
+            outputs[i] = state.output;
+        }
+        assert_eq!(state.round, 3);
+        if combiner == BeaconCombiner::Xor {
+            assert_eq!(outputs[0], 0xfeed_beef ^ 4);
+        }
+
+        let rounds: Vec<BeaconRound> = contributions
+            .iter()
+            .zip(outputs)
+            .enumerate()
+            .map(|(i, (values, output))| BeaconRound {
+                timestamp: 1_000 + 30 * (i as u64 + 1),
+                values,
+                output,
+            })
+            .collect();
+        assert_eq!(verify_beacon_chain(&genesis, &rounds, combiner), Ok(state));
+
+        // 篡改中间一轮的输出
+        let mut forged = rounds.clone();
+        forged[1].output ^= 1;
+        assert_eq!(
+            verify_beacon_chain(&genesis, &forged, combiner),
+            Err(Error::CalculationFailed)
+        );
+        // 错误的创世种子
+        let other = BeaconState::genesis(0, 1_000);
+        assert_eq!(
+            verify_beacon_chain(&other, &rounds, combiner),
+            Err(Error::CalculationFailed)
+        );
+    }
+}
+
+#[test]
+fn test_beacon_rejects_stale_timestamp() {
+    let genesis = BeaconState::genesis(1, 500);
+    let values = [1u128, 2];
+    assert_eq!(
+        next_beacon_round(&genesis, 500, &values, 2, BeaconCombiner::ChaCha20),
+        Err(Error::InvalidInput)
+    );
+    let r1 = next_beacon_round(&genesis, 501, &values, 2, BeaconCombiner::ChaCha20).unwrap();
+    let rounds = [
+        BeaconRound {
+            timestamp: 501,
+            values: &values,
+            output: r1.output,
+        },
+        BeaconRound {
+            timestamp: 501,
+            values: &values,
+            output: r1.output,
+        },
+    ];
+    assert_eq!(
+        verify_beacon_chain(&genesis, &rounds, BeaconCombiner::ChaCha20),
+        Err(Error::InvalidInput)
+    );
+}
+
+#[test]
+fn test_threshold_policies() {
+    let contributions = [
+        Some(10u128),
+        None,
+        Some(30),
+        None,
+        Some(50),
+        Some(60),
+        Some(70),
+        Some(80),
+    ];
+    let mut missing = [0usize; 4];
+
+    let report =
+        get_one_dd_rand_num_threshold(&contributions, 6, AbsenteePolicy::Exclude, &mut missing)
+            .unwrap();
+    assert_eq!(report.value, 10 ^ 30 ^ 50 ^ 60 ^ 70 ^ 80);
+    assert_eq!((report.present, report.missing), (6, 2));
+    assert_eq!(&missing[..2], &[1, 3]);
+
+    // 替代值互不相同，结果随上一轮信标变化
+    let a = get_one_dd_rand_num_threshold(
+        &contributions,
+        6,
+        AbsenteePolicy::SubstitutePrevious { beacon: 1 },
+        &mut missing,
+    )
+    .unwrap();
+    let b = get_one_dd_rand_num_threshold(
+        &contributions,
+        6,
+        AbsenteePolicy::SubstitutePrevious { beacon: 2 },
+        &mut missing,
+    )
+    .unwrap();
+    assert_ne!(a.value, report.value);
+    assert_ne!(a.value, b.value);
+
+    // 提交人数不足门限
+    let res =
+        get_one_dd_rand_num_threshold(&contributions, 7, AbsenteePolicy::Exclude, &mut missing);
+    assert!(matches!(res, Err(Error::NotEnoughParticipants)));
+    // 参与者数量不是2的n次幂
+    let res = get_one_dd_rand_num_threshold(
+        &contributions[..6],
+        4,
+        AbsenteePolicy::Exclude,
+        &mut missing,
+    );
+    assert!(matches!(res, Err(Error::InvalidInput)));
+
+    // 全员提交时与get_one_dd_rand_num一致
+    let full = [Some(100u128), Some(200), Some(300), Some(400)];
+    let report =
+        get_one_dd_rand_num_threshold(&full, 4, AbsenteePolicy::Penalize, &mut missing).unwrap();
+    let mut expected = 0u128;
+    get_one_dd_rand_num(&[100, 200, 300, 400], 4, &mut expected).unwrap();
+    assert_eq!(report.value, expected);
+}
+
+#[test]
+fn test_select_k_threshold_penalize() {
+    let contributions = [Some(3u128), None, Some(5), None];
+    let mut scratch = [0usize; 4];
+    let mut missing = [0usize; 2];
+
+    // Penalize：缺席者不能被选中
+    let mut output = [0usize; 2];
+    let report = select_k_threshold(
+        &contributions,
+        2,
+        AbsenteePolicy::Penalize,
+        &mut scratch,
+        &mut missing,
+        &mut output,
+    )
+    .unwrap();
+    assert_eq!(report.missing, 2);
+    output.sort_unstable();
+    assert_eq!(output, [0, 2]);
+    let mut output = [0usize; 3];
+    let res = select_k_threshold(
+        &contributions,
+        2,
+        AbsenteePolicy::Penalize,
+        &mut scratch,
+        &mut missing,
+        &mut output,
+    );
+    assert!(matches!(res, Err(Error::NotEnoughParticipants)));
+
+    // Exclude：缺席者仍有资格
+    let mut output = [0usize; 4];
+    select_k_threshold(
+        &contributions,
+        2,
+        AbsenteePolicy::Exclude,
+        &mut scratch,
+        &mut missing,
+        &mut output,
+    )
+    .unwrap();
+    output.sort_unstable();
+    assert_eq!(output, [0, 1, 2, 3]);
+
+    // 全员提交时与select_k_eligible一致
+    let full = [Some(1u128), Some(2), Some(3), Some(4)];
+    let mut a = [0usize; 2];
+    select_k_threshold(
+        &full,
+        4,
+        AbsenteePolicy::Exclude,
+        &mut scratch,
+        &mut missing,
+        &mut a,
+    )
+    .unwrap();
+    let mut b = [0usize; 2];
+    select_k_eligible(&[1, 2, 3, 4], 4, Eligibility::All, &mut scratch, &mut b).unwrap();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_shamir_split_and_reconstruct() {
+    let secret = SHAMIR_FIELD_PRIME - 1;
+    let coefficients = [987_654_321u128, 1 << 100, 42];
+    let mut shares = [ShamirShare::default(); 7];
+    let mut commitments = [0u128; 4];
+    shamir_split(secret, &coefficients, &mut shares, &mut commitments).unwrap();
+
+    for share in &shares {
+        assert!(feldman_verify_share(share, &commitments).unwrap());
+    }
+    // 任意4个份额都能恢复，3个不能
+    assert_eq!(shamir_reconstruct(&shares[..4]).unwrap(), secret);
+    assert_eq!(
+        shamir_reconstruct(&[shares[6], shares[1], shares[4], shares[2]]).unwrap(),
+        secret
+    );
+    assert_eq!(shamir_reconstruct(&shares).unwrap(), secret);
+    assert_ne!(shamir_reconstruct(&shares[..3]).unwrap(), secret);
+
+    // 无效输入
+    assert_eq!(
+        shamir_split(
+            SHAMIR_FIELD_PRIME,
+            &coefficients,
+            &mut shares,
+            &mut commitments
+        ),
+        Err(Error::InvalidInput)
+    );
+    assert_eq!(
+        shamir_split(secret, &coefficients, &mut shares[..3], &mut commitments),
+        Err(Error::InvalidInput)
+    );
+    assert_eq!(
+        shamir_reconstruct(&[shares[0], shares[0]]),
+        Err(Error::InvalidInput)
+    );
+}
+
+#[test]
+fn test_shamir_reconstruct_verified_skips_bad_shares() {
+    let secret = 0xdead_beef_u128;
+    let mut shares = [ShamirShare::default(); 5];
+    let mut commitments = [0u128; 3];
+    shamir_split(secret, &[5, 6], &mut shares, &mut commitments).unwrap();
+
+    // 伪造的份额无法通过验证
+    let mut collected = shares;
+    collected[0].value ^= 1;
+    collected[1].index = 9;
+    assert!(!feldman_verify_share(&collected[0], &commitments).unwrap());
+    assert!(!feldman_verify_share(&collected[1], &commitments).unwrap());
+    assert_eq!(
+        shamir_reconstruct_verified(&collected, &commitments).unwrap(),
+        secret
+    );
+
+    // 有效份额不足
+    collected[2].value = 0;
+    assert_eq!(
+        shamir_reconstruct_verified(&collected, &commitments),
+        Err(Error::NotEnoughParticipants)
+    );
+
+    // 承诺不是子群元素
+    let bad = [FELDMAN_GROUP_PRIME - 2, commitments[1], commitments[2]];
+    assert_eq!(
+        feldman_verify_share(&shares[0], &bad),
+        Err(Error::InvalidInput)
+    );
+
+    // 没有承诺时返回错误而不是panic
+    assert_eq!(
+        shamir_reconstruct_verified(&shares, &[]),
+        Err(Error::InvalidInput)
+    );
+}
+
+#[cfg(feature = "pedersen")]
+#[test]
+fn test_pedersen_split_and_reconstruct() {
+    let secret = u128::MAX - 12_345;
+    let coefficients: Vec<[u8; 64]> = (0..3u8).map(|i| [i.wrapping_mul(37) ^ 0x5a; 64]).collect();
+    let blindings: Vec<[u8; 64]> = (0..4u8).map(|i| [i.wrapping_mul(91) ^ 0xc3; 64]).collect();
+    let mut shares = [PedersenShare::default(); 7];
+    let mut commitments = [[0u8; 32]; 4];
+    pedersen_split(
+        secret,
+        &coefficients,
+        &blindings,
+        &mut shares,
+        &mut commitments,
+    )
+    .unwrap();
+    for share in &shares {
+        assert!(pedersen_verify_share(share, &commitments).unwrap());
+    }
+    assert!(pedersen_verify_opening(secret, &blindings[0], &commitments[0]).unwrap());
+    assert!(!pedersen_verify_opening(secret - 1, &blindings[0], &commitments[0]).unwrap());
+    assert!(!pedersen_verify_opening(secret, &blindings[1], &commitments[0]).unwrap());
+
+    assert_eq!(
+        pedersen_reconstruct_verified(&[shares[6], shares[1], shares[4], shares[2]], &commitments)
+            .unwrap(),
+        secret
+    );
+
+    // 同一秘密使用不同的盲化值，承诺完全不同
+    let other_blindings: Vec<[u8; 64]> = blindings.iter().map(|b| [b[0] ^ 1; 64]).collect();
+    let mut other = [[0u8; 32]; 4];
+    pedersen_split(
+        secret,
+        &coefficients,
+        &other_blindings,
+        &mut shares,
+        &mut other,
+    )
+    .unwrap();
+    assert_ne!(other[0], commitments[0]);
+
+    assert_eq!(
+        pedersen_split(
+            secret,
+            &coefficients,
+            &blindings[..3],
+            &mut shares,
+            &mut commitments
+        ),
+        Err(Error::InvalidInput)
+    );
+}
+
+#[cfg(feature = "pedersen")]
+#[test]
+fn test_pedersen_reconstruct_skips_bad_shares() {
+    let secret = 42u128;
+    let mut shares = [PedersenShare::default(); 5];
+    let mut commitments = [[0u8; 32]; 3];
+    pedersen_split(
+        secret,
+        &[[9u8; 64], [10u8; 64]],
+        &[[1u8; 64], [2u8; 64], [3u8; 64]],
+        &mut shares,
+        &mut commitments,
+    )
+    .unwrap();
+
+    // 篡改的份额、非规范标量和重复编号被跳过
+    let mut tampered = shares[0];
+    tampered.value[0] ^= 1;
+    let mut non_canonical = shares[1];
+    non_canonical.blinding = [0xff; 32];
+    let collected = [
+        tampered,
+        non_canonical,
+        shares[2],
+        shares[2],
+        shares[3],
+        shares[4],
+    ];
+    assert!(!pedersen_verify_share(&tampered, &commitments).unwrap());
+    assert!(!pedersen_verify_share(&non_canonical, &commitments).unwrap());
+    assert_eq!(
+        pedersen_reconstruct_verified(&collected, &commitments).unwrap(),
+        secret
+    );
+    assert_eq!(
+        pedersen_reconstruct_verified(&collected[..5], &commitments),
+        Err(Error::NotEnoughParticipants)
+    );
+
+    // 无效的点编码
+    let mut bad = commitments;
+    bad[1] = [0xff; 32];
+    assert_eq!(
+        pedersen_verify_share(&shares[0], &bad),
+        Err(Error::InvalidInput)
+    );
+}
+
+#[cfg(feature = "vrf")]
+fn hex_bytes<const N: usize>(hex: &str) -> [u8; N] {
+    let mut out = [0u8; N];
+    for (i, b) in out.iter_mut().enumerate() {
+        *b = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap();
+    }
+    out
+}
+
+/// RFC 9381 附录B.3 测试向量（公钥, alpha, 证明, beta）
+#[cfg(feature = "vrf")]
+const ECVRF_VECTORS: [(&str, &[u8], &str, &str); 2] = [
+    (
+        "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+        b"",
+        "8657106690b5526245a92b003bb079ccd1a92130477671f6fc01ad16f26f723f26f8a57ccaed74ee1b190bed1f479d9727d2d0f9b005a6e456a35d4fb0daab1268a1b0db10836d9826a528ca76567805",
+        "90cf1df3b703cce59e2a35b925d411164068269d7b2d29f3301c03dd757876ff66b71dda49d2de59d03450451af026798e8f81cd2e333de5cdf4f3e140fdd8ae",
+    ),
+    (
+        "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+        &[0x72],
+        "f3141cd382dc42909d19ec5110469e4feae18300e94f304590abdced48aed5933bf0864a62558b3ed7f2fea45c92a465301b3bbf5e3e54ddf2d935be3b67926da3ef39226bbc355bdc9850112c8f4b02",
+        "eb4440665d3891d668e7e0fcaf587f1b4bd7fbfe99d0eb2211ccec90496310eb5e33821bc613efb94db5e5b54c70a848a0bef4553a41befc57663b56373a5031",
+    ),
+];
+
+#[cfg(feature = "vrf")]
+#[test]
+fn test_ecvrf_verify_rfc9381_vectors() {
+    for (pk, alpha, pi, beta) in ECVRF_VECTORS {
+        let pk: [u8; 32] = hex_bytes(pk);
+        let pi: [u8; 80] = hex_bytes(pi);
+        let beta: [u8; 64] = hex_bytes(beta);
+        assert_eq!(ecvrf_verify(&pk, alpha, &pi), Ok(beta));
+
+        // 篡改输入或证明
+        assert_eq!(
+            ecvrf_verify(&pk, b"other", &pi),
+            Err(Error::CalculationFailed)
+        );
+        let mut bad = pi;
+        bad[40] ^= 1;
+        assert_eq!(
+            ecvrf_verify(&pk, alpha, &bad),
+            Err(Error::CalculationFailed)
+        );
+    }
+    // 小阶公钥（单位元）被拒绝
+    let mut identity = [0u8; 32];
+    identity[0] = 1;
+    let pi: [u8; 80] = hex_bytes(ECVRF_VECTORS[0].2);
+    assert_eq!(ecvrf_verify(&identity, b"", &pi), Err(Error::InvalidInput));
+}
+
+#[cfg(feature = "vrf")]
+#[test]
+fn test_vrf_contributions_adapters() {
+    let pk0: [u8; 32] = hex_bytes(ECVRF_VECTORS[0].0);
+    let pi0: [u8; 80] = hex_bytes(ECVRF_VECTORS[0].2);
+    let pk1: [u8; 32] = hex_bytes(ECVRF_VECTORS[1].0);
+    let pi1: [u8; 80] = hex_bytes(ECVRF_VECTORS[1].2);
+
+    // 测试向量的alpha不同，这里分别转换两位参与者的值
+    let c0 = [VrfContribution {
+        public_key: &pk0,
+        proof: &pi0,
+    }];
+    let mut v0 = [0u128; 1];
+    vrf_contributions_to_values(&c0, b"", &mut v0).unwrap();
+    let beta0: [u8; 64] = hex_bytes(ECVRF_VECTORS[0].3);
+    assert_eq!(v0[0], u128::from_le_bytes(beta0[..16].try_into().unwrap()));
+
+    let c1 = [VrfContribution {
+        public_key: &pk1,
+        proof: &pi1,
+    }];
+    let mut v1 = [0u128; 1];
+    vrf_contributions_to_values(&c1, &[0x72], &mut v1).unwrap();
+
+    let values = [v0[0], v1[0]];
+    let mut out = 0u128;
+    get_one_dd_rand_num(&values, 2, &mut out).unwrap();
+    assert_eq!(out, v0[0] ^ v1[0]);
+
+    // 展开为groups
+    let mut buffer = [0u128; 3];
+    vrf_contributions_to_groups(&c0, b"", 3, &mut buffer).unwrap();
+    assert_ne!(buffer[0], buffer[1]);
+    let mut again = [0u128; 3];
+    vrf_contributions_to_groups(&c0, b"", 3, &mut again).unwrap();
+    assert_eq!(buffer, again);
+
+    // 任何一个证明无效都会失败
+    let mixed = [
+        VrfContribution {
+            public_key: &pk0,
+            proof: &pi0,
+        },
+        VrfContribution {
+            public_key: &pk1,
+            proof: &pi0,
+        },
+    ];
+    let mut values = [0u128; 2];
+    assert!(vrf_contributions_to_values(&mixed, b"", &mut values).is_err());
+}
+
+#[test]
+fn test_analyze_coalition_bias() {
+    // 无联盟时没有偏差
+    let r = analyze_coalition_bias(16, 4, 0, CoalitionStrategy::SelectiveReveal).unwrap();
+    assert_eq!(r.honest_probability, BIAS_SCALE / 4);
+    assert_eq!(r.bias, 0);
+
+    // 无承诺时一个成员即可完全控制
+    let r = analyze_coalition_bias(1024, 1, 1, CoalitionStrategy::ChooseAfterReveal).unwrap();
+    assert_eq!(r.achievable_probability, BIAS_SCALE);
+
+    // 中止重抽：1 - (3/4)^3 = 37/64
+    let r = analyze_coalition_bias(16, 4, 2, CoalitionStrategy::AbortAndRetry).unwrap();
+    assert_eq!(r.outcomes, 3);
+    assert_eq!(r.achievable_probability, 578_125_000);
+    assert_eq!(r.bias, 578_125_000 - 250_000_000);
+
+    // 选择性揭示：1 - (1 - 4/16)^2 = 7/16
+    let r = analyze_coalition_bias(16, 2, 2, CoalitionStrategy::SelectiveReveal).unwrap();
+    assert_eq!(r.achievable_probability, 437_500_000);
+
+    // 偏差随联盟人数单调不减
+    let mut last = 0;
+    for c in 0..=16 {
+        let r = analyze_coalition_bias(16, 1, c, CoalitionStrategy::AbortAndRetry).unwrap();
+        assert!(r.achievable_probability >= last);
+        last = r.achievable_probability;
+    }
+    assert!(analyze_coalition_bias(12, 1, 1, CoalitionStrategy::AbortAndRetry).is_err());
+    assert!(analyze_coalition_bias(16, 1, 17, CoalitionStrategy::AbortAndRetry).is_err());
+}
+
+#[test]
+fn test_simulate_coalition_bias_matches_analysis() {
+    let trials = 4_000;
+    for (n, k, c, strategy) in [
+        (16usize, 1usize, 2usize, CoalitionStrategy::SelectiveReveal),
+        (32, 4, 2, CoalitionStrategy::AbortAndRetry),
+        (8, 2, 1, CoalitionStrategy::ChooseAfterReveal),
+    ] {
+        let sim = simulate_coalition_bias(n, k, c, strategy, 0x5eed, trials).unwrap();
+        let exact = analyze_coalition_bias(n, k, c, strategy).unwrap();
+        let tolerance = 30_000_000; // 0.03
+        assert!(sim.honest_probability.abs_diff(exact.honest_probability) < tolerance);
+        assert!(
+            sim.achievable_probability
+                .abs_diff(exact.achievable_probability)
+                < tolerance,
+            "sim={:?} exact={:?}",
+            sim,
+            exact
+        );
+        test_log!("bias n={} k={} c={} {:?} sim={:?}", n, k, c, strategy, sim);
+    }
+    assert!(simulate_coalition_bias(128, 1, 1, CoalitionStrategy::AbortAndRetry, 0, 1).is_err());
+    assert!(simulate_coalition_bias(16, 1, 11, CoalitionStrategy::SelectiveReveal, 0, 1).is_err());
+}
+
+#[test]
+fn test_statistics_detect_non_uniform_sources() {
+    // Wilson–Hilferty近似与卡方分布表（α = 0.001）相差不超过1%
+    for (df, table) in [
+        (9u64, 27_877_000_000u128),
+        (15, 37_697_000_000),
+        (63, 103_442_000_000),
+    ] {
+        let approx = chi_square_critical_value(df).unwrap();
+        assert!(
+            approx.abs_diff(table) * 100 < table,
+            "df={} approx={}",
+            df,
+            approx
+        );
+    }
+
+    // 只落在前一半区间的样本
+    let mut rng = DdRng::new(1);
+    let mut half: Vec<u128> = (0..2048).map(|_| rng.next_u128() >> 1).collect();
+    let report = randomness_suite(&mut half, 16).unwrap();
+    assert!(!report.chi_square.passed);
+    assert!(!report.kolmogorov_smirnov.passed);
+
+    // 单调递增的样本：分布均匀但前后强相关
+    let ramp: Vec<u128> = (0..2048u128).map(|i| (i << 117) | 1).collect();
+    assert!(!serial_correlation(&ramp).unwrap().passed);
+
+    // 总是选中前k个索引
+    let mut counts = [0u64; 16];
+    counts[..4].iter_mut().for_each(|c| *c = 1000);
+    assert!(!selection_frequency(&counts, 4).unwrap().passed);
+
+    assert!(chi_square_uniform(&[5]).is_err());
+    assert!(chi_square_uniform(&[0, 0]).is_err());
+    assert!(ks_uniform(&mut [1]).is_err());
+    assert!(serial_correlation(&[7, 7, 7]).is_err());
+    assert!(selection_frequency(&[3, 3, 3], 2).is_err());
+    assert!(randomness_suite(&mut [0u128; 10], 16).is_err());
+}
+
+#[test]
+fn test_randomness_of_dd_random_apis() {
+    const TRIALS: u128 = 4096;
+    // 每次试验的贡献值由确定性种子派生
+    let contribution =
+        |seed: u128, trial: u128, j: u128| crate::math::mix_u128(seed, trial * 64 + j);
+
+    // get_one_dd_rand_num
+    let mut samples: Vec<u128> = (0..TRIALS)
+        .map(|t| {
+            let values: Vec<u128> = (0..4).map(|j| contribution(1, t, j)).collect();
+            let mut out = 0u128;
+            get_one_dd_rand_num(&values, 4, &mut out).unwrap();
+            out
+        })
+        .collect();
+    let report = randomness_suite(&mut samples, 64).unwrap();
+    test_log!("get_one_dd_rand_num {:?}", report);
+    assert!(report.passed());
+
+    // get_one_dd_3d_rand_num
+    let mut counts = [0u64; 16];
+    for t in 0..TRIALS {
+        let values: Vec<u128> = (0..4).map(|j| contribution(2, t, j)).collect();
+        let mut out = 0u128;
+        get_one_dd_3d_rand_num(&values, 4, 16, &mut out).unwrap();
+        counts[out as usize] += 1;
+    }
+    assert!(chi_square_uniform(&counts).unwrap().passed);
+
+    // DdRng
+    let mut rng = DdRng::new(contribution(3, 0, 0));
+    let mut samples: Vec<u128> = (0..TRIALS).map(|_| rng.next_u128()).collect();
+    assert!(randomness_suite(&mut samples, 64).unwrap().passed());
+
+    // 信标的两种组合器：相同贡献值逐轮链接
+    for combiner in [BeaconCombiner::Xor, BeaconCombiner::ChaCha20] {
+        let mut state = BeaconState::genesis(contribution(4, 0, 0), 0);
+        let mut samples = Vec::new();
+        for t in 1..=TRIALS {
+            let values: Vec<u128> = (0..4).map(|j| contribution(5, t, j)).collect();
+            state = next_beacon_round(&state, t as Timestamp, &values, 4, combiner).unwrap();
+            samples.push(state.output);
+        }
+        let report = randomness_suite(&mut samples, 64).unwrap();
+        test_log!("beacon {:?} {:?}", combiner, report);
+        assert!(report.passed());
+    }
+
+    // dd_shuffle：元素0最终位置的分布
+    let mut counts = [0u64; 16];
+    for t in 0..TRIALS {
+        let values: Vec<u128> = (0..4).map(|j| contribution(6, t, j)).collect();
+        let mut items: Vec<usize> = (0..16).collect();
+        dd_shuffle(&values, 4, &mut items).unwrap();
+        counts[items.iter().position(|&x| x == 0).unwrap()] += 1;
+    }
+    assert!(chi_square_uniform(&counts).unwrap().passed);
+
+    // get_k_dd_rand_num_large
+    let n = 1000u64;
+    let mut bitset = vec![0u64; dd_bitset_len(n)];
+    let mut counts = vec![0u64; 50];
+    for t in 0..TRIALS / 4 {
+        let values: Vec<u128> = (0..4).map(|j| contribution(7, t, j)).collect();
+        let mut output = [0u64; 10];
+        get_k_dd_rand_num_large(&values, 4, n, &mut bitset, &mut output).unwrap();
+        for &x in &output {
+            counts[(x / 20) as usize] += 1;
+        }
+    }
+    assert!(chi_square_uniform(&counts).unwrap().passed);
+
+    // 彩票：3D各位数字
+    let mut counts = [0u64; 10];
+    for t in 0..TRIALS {
+        let values: Vec<u128> = (0..4).map(|j| contribution(8, t, j)).collect();
+        let mut digits = [0u8; 3];
+        get_dd_lottery_draw(&values, 4, LotteryFormat::FUCAI_3D, &mut digits).unwrap();
+        digits.iter().for_each(|&d| counts[d as usize] += 1);
+    }
+    assert!(chi_square_uniform(&counts).unwrap().passed);
+}
+
+#[test]
+fn test_get_k_dd_rand_num_selection_frequency() {
+    // 每个索引被选中的频率，以及第一个输出位置上的索引分布
+    let (n, k) = (16usize, 4usize);
+    let mut counts = [0u64; 16];
+    let mut first = [0u64; 16];
+    for t in 0..4000u128 {
+        let rows: Vec<Vec<u128>> = (0..n as u128)
+            .map(|j| {
+                (0..k as u128)
+                    .map(|x| crate::math::mix_u128(9, (t * 16 + j) * 4 + x))
+                    .collect()
+            })
+            .collect();
+        let groups: Vec<&[u128]> = rows.iter().map(|r| r.as_slice()).collect();
+        let mut output = [0usize; 4];
+        get_k_dd_rand_num(&groups, n, k, &mut output).unwrap();
+        output.iter().for_each(|&i| counts[i] += 1);
+        first[output[0]] += 1;
+    }
+    let result = selection_frequency(&counts, k).unwrap();
+    test_log!("selection frequency counts={:?} {:?}", counts, result);
+    assert!(result.passed);
+    assert!(chi_square_uniform(&first).unwrap().passed);
+}
+
+#[test]
+fn test_sha256_vectors() {
+    let hex = |d: [u8; 32]| {
+        d.iter()
+            .map(|b| alloc::format!("{:02x}", b))
+            .collect::<alloc::string::String>()
+    };
+    assert_eq!(
+        hex(crate::sha256::sha256(b"")),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert_eq!(
+        hex(crate::sha256::sha256(b"abc")),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+        hex(crate::sha256::sha256(
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+        )),
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+    );
+    // 分段输入与一次性输入一致
+    let mut hasher = crate::sha256::Sha256::new();
+    for chunk in [
+        b"abcdbcdecdefdefgefgh".as_slice(),
+        b"fghighijhijkijkljklmklmnlmnomnopnopq",
+    ] {
+        hasher.update(chunk);
+    }
+    assert_eq!(
+        hasher.finalize(),
+        crate::sha256::sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")
+    );
+}
+
+#[test]
+fn test_verify_transcript() {
+    let participants = [1u64, 2, 3, 4];
+    let values = [10u128, 20, 30, 40];
+    let commitments: Vec<[u8; 32]> = participants
+        .iter()
+        .zip(values.iter())
+        .map(|(&id, v)| contribution_commitment(id, core::slice::from_ref(v)))
+        .collect();
+
+    // 信标轮次
+    let genesis = BeaconState::genesis(0x5eed, 0);
+    let round = next_beacon_round(&genesis, 1, &values, 4, BeaconCombiner::ChaCha20).unwrap();
+    let outputs = [round.output];
+    let beacon = DrawTranscript {
+        version: DRAW_TRANSCRIPT_VERSION,
+        algorithm: DrawAlgorithm::BeaconRound {
+            combiner: BeaconCombiner::ChaCha20,
+            previous_output: 0x5eed,
+        },
+        participants: &participants,
+        contributions: &values,
+        commitments: &commitments,
+        n: 4,
+        k: 0,
+        exclusions: &[],
+        outputs: &outputs,
+    };
+    let digest = verify_transcript(&beacon).unwrap();
+
+    // 编码后的摘要一致，编码长度与字段一致
+    let mut buf = vec![0u8; beacon.encoded_len()];
+    assert_eq!(
+        beacon.encode(&mut buf).unwrap(),
+        4 + 2 + 1 + 1 + 16 + 16 + 5 * 8 + 4 * 8 + 4 * 16 + 4 * 32 + 16
+    );
+    assert_eq!(transcript_digest(&buf), digest);
+    assert_eq!(&buf[..4], b"DDTR");
+    assert!(beacon.encode(&mut buf[..10]).is_err());
+
+    // 改变组合器：摘要不同，验证失败
+    let xor = DrawTranscript {
+        algorithm: DrawAlgorithm::BeaconRound {
+            combiner: BeaconCombiner::Xor,
+            previous_output: 0x5eed,
+        },
+        ..beacon
+    };
+    assert_ne!(xor.digest(), digest);
+    assert_eq!(verify_transcript(&xor), Err(Error::CalculationFailed));
+
+    // 揭示的值与承诺不符
+    let tampered = [10u128, 20, 31, 40];
+    assert_eq!(
+        verify_transcript(&DrawTranscript {
+            contributions: &tampered,
+            ..beacon
+        }),
+        Err(Error::CalculationFailed)
+    );
+
+    // 带排除列表的k选择
+    let rows = [5u128, 6, 7, 8, 9, 10, 11, 12];
+    let groups: Vec<&[u128]> = rows.chunks(2).collect();
+    let mut selected = [0usize; 2];
+    get_k_dd_rand_num_with_whitelist(&groups, 4, 2, &[1], &mut selected).unwrap();
+    let outputs: Vec<u128> = selected.iter().map(|&i| i as u128).collect();
+    let k_draw = DrawTranscript {
+        version: DRAW_TRANSCRIPT_VERSION,
+        algorithm: DrawAlgorithm::KRandNum,
+        participants: &participants,
+        contributions: &rows,
+        commitments: &[],
+        n: 4,
+        k: 2,
+        exclusions: &[1],
+        outputs: &outputs,
+    };
+    assert!(verify_transcript(&k_draw).is_ok());
+    // 排除列表是记录的一部分
+    assert_ne!(
+        DrawTranscript {
+            exclusions: &[],
+            ..k_draw
+        }
+        .digest(),
+        k_draw.digest()
+    );
+
+    // 结构无效
+    assert_eq!(
+        verify_transcript(&DrawTranscript {
+            version: 2,
+            ..k_draw
+        }),
+        Err(Error::InvalidInput)
+    );
+    assert_eq!(
+        verify_transcript(&DrawTranscript {
+            exclusions: &[2, 1],
+            ..k_draw
+        }),
+        Err(Error::InvalidInput)
+    );
+    assert_eq!(
+        verify_transcript(&DrawTranscript {
+            participants: &participants[..3],
+            ..k_draw
+        }),
+        Err(Error::InvalidInput)
+    );
+    assert_eq!(
+        verify_transcript(&DrawTranscript {
+            exclusions: &[0],
+            ..beacon
+        }),
+        Err(Error::InvalidInput)
+    );
+
+    // 不可信记录中的k超过n或n*k溢出时返回错误而不是panic
+    let many = [0u128; 300];
+    let outputs = [0u128; 300];
+    assert_eq!(
+        verify_transcript(&DrawTranscript {
+            participants: &participants[..1],
+            contributions: &many,
+            n: 1,
+            k: 300,
+            exclusions: &[],
+            outputs: &outputs,
+            ..k_draw
+        }),
+        Err(Error::InvalidInput)
+    );
+    assert_eq!(
+        verify_transcript(&DrawTranscript {
+            contributions: &[],
+            k: usize::MAX / 2 + 1,
+            exclusions: &[],
+            ..k_draw
+        }),
+        Err(Error::InvalidInput)
+    );
+}
+
+#[test]
+fn test_probabilistic_serial_and_bvn() {
+    // a: 0>1>2, b: 0>2>1, c: 1>0>2
+    let (a, b, c) = ([0usize, 1, 2], [0usize, 2, 1], [1usize, 0, 2]);
+    let preferences = [a.as_slice(), b.as_slice(), c.as_slice()];
+    let mut matrix = [0u128; 9];
+    let d = probabilistic_serial(&preferences, 3, &mut matrix).unwrap();
+    assert_eq!(d, 4);
+    assert_eq!(matrix, [2, 1, 1, 2, 0, 2, 0, 3, 1]);
+
+    // BvN分解还原矩阵
+    let mut weights = [0u128; 8];
+    let mut permutations = [0usize; 24];
+    let terms = birkhoff_von_neumann(&matrix, 3, &mut weights, &mut permutations).unwrap();
+    let mut rebuilt = [0u128; 9];
+    for t in 0..terms {
+        for r in 0..3 {
+            rebuilt[r * 3 + permutations[t * 3 + r]] += weights[t];
+        }
+    }
+    assert_eq!(rebuilt, matrix);
+    assert!(birkhoff_von_neumann(&[1, 2, 2, 2], 2, &mut weights, &mut permutations).is_err());
+    assert!(birkhoff_von_neumann(&matrix, 3, &mut weights[..1], &mut permutations).is_err());
+
+    // 抽样频率与分数分配一致
+    let trials = 4000u128;
+    let mut counts = [0u128; 9];
+    for t in 0..trials {
+        let values = [crate::math::mix_u128(46, t), 1, 2, 3];
+        let mut assignment = [None; 3];
+        sample_probabilistic_serial(&values, 4, &preferences, 3, &mut assignment).unwrap();
+        for (i, item) in assignment.iter().enumerate() {
+            counts[i * 3 + item.unwrap()] += 1;
+        }
+    }
+    for (count, &p) in counts.iter().zip(&matrix) {
+        let expected = trials * p / d;
+        assert!(
+            count.abs_diff(expected) < trials / 25,
+            "counts={:?}",
+            counts
+        );
+    }
+
+    // 物品少于参与者、偏好不完整时仍能分解
+    let short = [0usize];
+    let other = [1usize, 0];
+    let preferences = [short.as_slice(), short.as_slice(), other.as_slice()];
+    let mut matrix = [0u128; 6];
+    let d = probabilistic_serial(&preferences, 2, &mut matrix).unwrap();
+    assert_eq!((d, matrix), (2, [1, 0, 1, 0, 0, 2]));
+    let mut assignment = [None; 3];
+    sample_probabilistic_serial(&[7, 8], 2, &preferences, 2, &mut assignment).unwrap();
+    assert_eq!(assignment[2], Some(1));
+    assert!(assignment[0].is_some() != assignment[1].is_some());
+
+    let dup = [0usize, 0];
+    assert!(probabilistic_serial(&[dup.as_slice()], 2, &mut [0; 2]).is_err());
+}
+
+#[test]
+fn test_random_serial_dictatorship() {
+    let p = [0usize, 1, 2, 3];
+    let preferences = [p.as_slice(); 4];
+    let mut first = [0u64; 4];
+    for t in 0..2000u128 {
+        let values = [crate::math::mix_u128(47, t), 5];
+        let mut order = [0usize; 4];
+        let mut assignment = [None; 4];
+        random_serial_dictatorship(&values, 2, &preferences, 4, &mut order, &mut assignment)
+            .unwrap();
+        // 相同偏好下第r个挑选者得到第r个物品
+        for (rank, &agent) in order.iter().enumerate() {
+            assert_eq!(assignment[agent], Some(rank));
+        }
+        first[order[0]] += 1;
+    }
+    assert!(chi_square_uniform(&first).unwrap().passed);
+
+    let mut order = [0usize; 4];
+    let mut assignment = [None; 4];
+    let bad = [4usize];
+    let preferences = [p.as_slice(), p.as_slice(), p.as_slice(), bad.as_slice()];
+    assert!(
+        random_serial_dictatorship(&[1, 2], 2, &preferences, 4, &mut order, &mut assignment)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_ef1_allocations() {
+    for case in 0..200u128 {
+        let n = 2 + (case % 5) as usize;
+        let m = (case % 17) as usize;
+        let rows: Vec<Vec<u128>> = (0..n)
+            .map(|i| {
+                (0..m)
+                    .map(|j| crate::math::mix_u128(case, (i * 64 + j) as u128) % 50)
+                    .collect()
+            })
+            .collect();
+        let valuations: Vec<&[u128]> = rows.iter().map(|r| r.as_slice()).collect();
+
+        // 以确定性种子打乱的挑选顺序
+        let mut order: Vec<usize> = (0..n).collect();
+        dd_shuffle(&[case, 1], 2, &mut order).unwrap();
+
+        let mut allocation = vec![0usize; m];
+        round_robin_allocation(&valuations, Some(&order), &mut allocation).unwrap();
+        assert!(
+            is_ef1(&valuations, &allocation).unwrap(),
+            "rr case={}",
+            case
+        );
+        // 每位参与者分得的物品数相差不超过1
+        let sizes: Vec<usize> = (0..n)
+            .map(|a| allocation.iter().filter(|&&x| x == a).count())
+            .collect();
+        assert!(sizes.iter().max().unwrap() - sizes.iter().min().unwrap() <= 1);
+
+        envy_cycle_elimination(&valuations, Some(&order), &mut allocation).unwrap();
+        assert!(
+            is_ef1(&valuations, &allocation).unwrap(),
+            "ece case={}",
+            case
+        );
+    }
+
+    // 挑选顺序决定谁先拿到最好的物品
+    let v = [9u128, 5, 1];
+    let valuations = [v.as_slice(), v.as_slice()];
+    let mut allocation = [0usize; 3];
+    round_robin_allocation(&valuations, Some(&[1, 0]), &mut allocation).unwrap();
+    assert_eq!(allocation, [1, 0, 1]);
+    assert!(round_robin_allocation(&valuations, Some(&[1, 1]), &mut allocation).is_err());
+    assert!(round_robin_allocation(&valuations, Some(&[0]), &mut allocation).is_err());
+
+    // 包络消除：物品0给参与者0、物品1给参与者1后两人互相嫉妒，
+    // 分配物品2之前交换物品包消除嫉妒环
+    let v0 = [4u128, 6, 1];
+    let v1 = [7u128, 1, 1];
+    envy_cycle_elimination(&[v0.as_slice(), v1.as_slice()], None, &mut allocation).unwrap();
+    assert_eq!(allocation, [1, 0, 0]);
+}
+
+#[test]
+fn test_efx_checker() {
+    // 轮流挑选满足EF1但不一定满足EFX
+    let v0 = [10u128, 1, 1, 0];
+    let v1 = [10u128, 4, 4, 1];
+    let valuations = [v0.as_slice(), v1.as_slice()];
+    let mut allocation = [0usize; 4];
+    round_robin_allocation(&valuations, None, &mut allocation).unwrap();
+    assert_eq!(allocation, [0, 1, 0, 1]);
+    assert!(is_ef1(&valuations, &allocation).unwrap());
+    // 参与者1：自己5，对方11，去掉对方估值最低的正估值物品（4）后仍为7 > 5
+    assert!(!is_efx(&valuations, &allocation).unwrap());
+    assert!(is_efx(&valuations, &[0, 1, 1, 1]).unwrap());
+
+    // 零估值物品不影响EFX
+    let z = [3u128, 0, 3];
+    let valuations = [z.as_slice(), z.as_slice()];
+    assert!(is_efx(&valuations, &[0, 1, 1]).unwrap());
+    assert!(is_efx(&valuations, &[0, 0, 1]).unwrap());
+    assert!(is_ef1(&valuations, &[0, 2, 1]).is_err());
+}
+
+#[test]
+fn test_maximum_nash_welfare_matches_brute_force() {
+    for case in 0..60u128 {
+        let n = 2 + (case % 2) as usize;
+        let m = 1 + (case % 6) as usize;
+        let rows: Vec<Vec<u128>> = (0..n)
+            .map(|i| {
+                (0..m)
+                    .map(|j| crate::math::mix_u128(case + 480, (i * 16 + j) as u128) % 8)
+                    .collect()
+            })
+            .collect();
+        let valuations: Vec<&[u128]> = rows.iter().map(|r| r.as_slice()).collect();
+
+        // 按字典序枚举所有分配，保留第一个最优解
+        let mut best: Option<((usize, u128), Vec<usize>)> = None;
+        for code in 0..n.pow(m as u32) {
+            let alloc: Vec<usize> = (0..m).rev().map(|j| code / n.pow(j as u32) % n).collect();
+            let mut utilities = vec![0u128; n];
+            for (item, &a) in alloc.iter().enumerate() {
+                utilities[a] += rows[a][item];
+            }
+            let positive: Vec<u128> = utilities.into_iter().filter(|&u| u > 0).collect();
+            let key = (positive.len(), positive.iter().product::<u128>());
+            let better = match &best {
+                Some((b, _)) => key > *b,
+                None => true,
+            };
+            if better {
+                best = Some((key, alloc));
+            }
+        }
+        let (key, expected) = best.unwrap();
+
+        let mut allocation = vec![0usize; m];
+        let mut utilities = vec![0u128; n];
+        let welfare = maximum_nash_welfare(&valuations, &mut allocation, &mut utilities).unwrap();
+        assert_eq!(allocation, expected, "case={} rows={:?}", case, rows);
+        assert_eq!(
+            (welfare.positive_agents, welfare.product_u128().unwrap()),
+            key
+        );
+        if key.0 == n {
+            assert!(is_ef1(&valuations, &allocation).unwrap());
+        }
+    }
+}
+
+#[test]
+fn test_maximum_nash_welfare_large_products() {
+    // 8个接近2^100的效用之积超过u128，仍能精确比较
+    let big = 1u128 << 100;
+    let rows: Vec<Vec<u128>> = (0..8)
+        .map(|i| {
+            (0..8)
+                .map(|j| if i == j { big + i as u128 } else { 1 })
+                .collect()
+        })
+        .collect();
+    let valuations: Vec<&[u128]> = rows.iter().map(|r| r.as_slice()).collect();
+    let mut allocation = [0usize; 8];
+    let mut utilities = [0u128; 8];
+    let welfare = maximum_nash_welfare(&valuations, &mut allocation, &mut utilities).unwrap();
+    assert_eq!(allocation, [0, 1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(welfare.positive_agents, 8);
+    assert_eq!(welfare.product_u128(), None);
+    // 2^800 的最高分段
+    assert_eq!(welfare.product[NASH_WELFARE_LIMBS - 13], 1 << 32);
+
+    // 参与者1对所有物品估值为0：最大化其余参与者
+    let v0 = [3u128, 1];
+    let v1 = [0u128, 0];
+    let v2 = [1u128, 3];
+    let mut allocation = [0usize; 2];
+    let mut utilities = [0u128; 3];
+    let welfare = maximum_nash_welfare(
+        &[v0.as_slice(), v1.as_slice(), v2.as_slice()],
+        &mut allocation,
+        &mut utilities,
+    )
+    .unwrap();
+    assert_eq!(
+        (welfare.positive_agents, welfare.product_u128()),
+        (2, Some(9))
+    );
+    assert_eq!(allocation, [0, 2]);
+
+    let nine = [[1u128; 1]; 9];
+    let too_many: Vec<&[u128]> = nine.iter().map(|r| r.as_slice()).collect();
+    assert!(maximum_nash_welfare(&too_many, &mut [0; 1], &mut [0; 9]).is_err());
+}
+
+#[test]
+fn test_chore_division_compensation() {
+    let asks = [40i128, 25, 60, 25];
+    let mut output = [0i128; 4];
+    let assignee = calculate_chore_division_equal_weights(&asks, &mut output).unwrap();
+    // 报价相同时索引小者承担
+    assert_eq!(assignee, 1);
+    assert_eq!(output.iter().sum::<i128>(), 0);
+    assert!(output[assignee] > 0);
+    // 报价越高的非承担者付得越多
+    assert!(output[2] < output[0]);
+
+    // 与商品版本互为镜像
+    let bids = asks.map(|a| -a);
+    let mut goods = [0i128; 4];
+    calculate_fair_division_equal_weights(&bids, &mut goods).unwrap();
+    assert_eq!(goods, output);
+
+    let weights = [1i128, 2, 1, 1];
+    let assignee = calculate_chore_division_weighted(&asks, &weights, &mut output).unwrap();
+    assert_eq!(assignee, 1);
+    assert_eq!(output.iter().sum::<i128>(), 0);
+
+    assert_eq!(
+        calculate_chore_division_equal_weights(&[5], &mut [0]),
+        Err(Error::NotEnoughParticipants)
+    );
+    assert!(calculate_chore_division_equal_weights(&[i128::MIN, 0], &mut [0, 0]).is_err());
+
+    // 没有参与者数上限，结果与对取反报价的公平分配一致
+    let asks: Vec<i128> = (0..1_500u128)
+        .map(|i| (crate::math::mix_u128(49, i) % 10_000) as i128)
+        .collect();
+    let bids: Vec<i128> = asks.iter().map(|&a| -a).collect();
+    let mut expected = vec![0i128; asks.len()];
+    calculate_fair_division_equal_weights(&bids, &mut expected).unwrap();
+    let mut output = vec![0i128; asks.len()];
+    calculate_chore_division_equal_weights(&asks, &mut output).unwrap();
+    assert_eq!(output, expected);
+    assert_eq!(output.iter().sum::<i128>(), 0);
+}
+
+#[test]
+fn test_round_robin_chores_ef1() {
+    for case in 0..200u128 {
+        let n = 2 + (case % 4) as usize;
+        let m = (case % 19) as usize;
+        let rows: Vec<Vec<u128>> = (0..n)
+            .map(|i| {
+                (0..m)
+                    .map(|j| crate::math::mix_u128(case + 490, (i * 64 + j) as u128) % 30)
+                    .collect()
+            })
+            .collect();
+        let costs: Vec<&[u128]> = rows.iter().map(|r| r.as_slice()).collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        dd_shuffle(&[case, 2], 2, &mut order).unwrap();
+        let mut allocation = vec![0usize; m];
+        round_robin_chores(&costs, Some(&order), &mut allocation).unwrap();
+        assert!(is_ef1_chores(&costs, &allocation).unwrap(), "case={}", case);
+    }
+
+    // 一人承担全部家务不满足EF1
+    let c = [3u128, 3, 3];
+    let costs = [c.as_slice(), c.as_slice()];
+    assert!(!is_ef1_chores(&costs, &[0, 0, 0]).unwrap());
+    assert!(is_ef1_chores(&costs, &[0, 0, 1]).unwrap());
+    assert!(is_ef1_chores(&costs, &[0, 0, 2]).is_err());
+}
+
+#[test]
+fn test_claims_division_classic_examples() {
+    // 《塔木德》Ketubot 93a：三位遗孀的婚约索赔为100、200、300
+    let claims = [100u128, 200, 300];
+    let mut awards = [0u128; 3];
+    for (estate, expected) in [
+        (100u128, [34u128, 33, 33]),
+        (200, [50, 75, 75]),
+        (300, [50, 100, 150]),
+    ] {
+        calculate_claims_division(&claims, estate, ClaimsRule::Talmud, &mut awards).unwrap();
+        assert_eq!(awards, expected, "estate={}", estate);
+    }
+    // 遗产超过总索赔一半时，损失按半索赔额等额分摊
+    calculate_claims_division(&claims, 500, ClaimsRule::Talmud, &mut awards).unwrap();
+    assert_eq!(awards, [66, 167, 267]);
+
+    calculate_claims_division(&claims, 300, ClaimsRule::Proportional, &mut awards).unwrap();
+    assert_eq!(awards, [50, 100, 150]);
+    calculate_claims_division(&claims, 100, ClaimsRule::Proportional, &mut awards).unwrap();
+    assert_eq!(awards, [17, 33, 50]);
+    calculate_claims_division(
+        &claims,
+        300,
+        ClaimsRule::ConstrainedEqualAwards,
+        &mut awards,
+    )
+    .unwrap();
+    assert_eq!(awards, [100, 100, 100]);
+    calculate_claims_division(
+        &claims,
+        250,
+        ClaimsRule::ConstrainedEqualAwards,
+        &mut awards,
+    )
+    .unwrap();
+    assert_eq!(awards, [84, 83, 83]);
+    calculate_claims_division(
+        &claims,
+        300,
+        ClaimsRule::ConstrainedEqualLosses,
+        &mut awards,
+    )
+    .unwrap();
+    assert_eq!(awards, [0, 100, 200]);
+    // 随机到达：平均6种到达顺序的先到先得结果
+    calculate_claims_division(&claims, 300, ClaimsRule::RandomArrival, &mut awards).unwrap();
+    assert_eq!(awards, [50, 100, 150]);
+
+    assert_eq!(
+        calculate_claims_division(&claims, 601, ClaimsRule::Proportional, &mut awards),
+        Err(Error::InvalidInput)
+    );
+    assert_eq!(
+        calculate_claims_division(&claims, 1, ClaimsRule::Talmud, &mut [0u128; 2]),
+        Err(Error::InvalidInput)
+    );
+    let many = [1u128; MAX_RANDOM_ARRIVAL_CLAIMANTS + 1];
+    let mut out = [0u128; MAX_RANDOM_ARRIVAL_CLAIMANTS + 1];
+    assert_eq!(
+        calculate_claims_division(&many, 5, ClaimsRule::RandomArrival, &mut out),
+        Err(Error::InvalidInput)
+    );
+}
+
+#[test]
+fn test_claims_division_sums_to_estate() {
+    let rules = [
+        ClaimsRule::Proportional,
+        ClaimsRule::ConstrainedEqualAwards,
+        ClaimsRule::ConstrainedEqualLosses,
+        ClaimsRule::Talmud,
+        ClaimsRule::RandomArrival,
+    ];
+    for case in 0..300u128 {
+        let n = 1 + (case % 7) as usize;
+        let claims: Vec<u128> = (0..n)
+            .map(|i| crate::math::mix_u128(case + 500, i as u128) % 1_000)
+            .collect();
+        let total: u128 = claims.iter().sum();
+        let estate = crate::math::mix_u128(case + 500, 99) % (total + 1);
+        let mut awards = vec![0u128; n];
+        for rule in rules {
+            calculate_claims_division(&claims, estate, rule, &mut awards).unwrap();
+            assert_eq!(
+                awards.iter().sum::<u128>(),
+                estate,
+                "case={} rule={:?}",
+                case,
+                rule
+            );
+            assert!(
+                awards.iter().zip(&claims).all(|(a, c)| a <= c),
+                "case={} rule={:?}",
+                case,
+                rule
+            );
+        }
+
+        // 比例规则与精确值相差不到1个单位
+        calculate_claims_division(&claims, estate, ClaimsRule::Proportional, &mut awards).unwrap();
+        for (a, c) in awards.iter().zip(&claims) {
+            assert!(
+                (a * total).abs_diff(c * estate) < total.max(1),
+                "case={}",
+                case
+            );
+        }
+
+        // 两位索赔人时随机到达规则与塔木德规则（争衣原则）一致，至多相差舍入的1个单位
+        if n == 2 {
+            let mut talmud = vec![0u128; 2];
+            calculate_claims_division(&claims, estate, ClaimsRule::Talmud, &mut talmud).unwrap();
+            calculate_claims_division(&claims, estate, ClaimsRule::RandomArrival, &mut awards)
+                .unwrap();
+            assert!(awards[0].abs_diff(talmud[0]) <= 1, "case={}", case);
+        }
+    }
+
+    // 大额索赔不溢出
+    let claims = [u128::MAX / 2, u128::MAX / 3];
+    let estate = u128::MAX / 4;
+    let mut awards = [0u128; 2];
+    for rule in rules {
+        calculate_claims_division(&claims, estate, rule, &mut awards).unwrap();
+        assert_eq!(awards[0] + awards[1], estate);
+    }
+}
@@ -0,0 +1,144 @@
+//! 缺席容错的门限随机数。
+//!
+//! 普通的随机数函数要求n位参与者全部提交，任何一人缺席都会因长度不匹配而失败。
+//! 门限模式下只要至少t位参与者提交，就按照明确的缺席策略继续产生输出，
+//! 并报告缺席者名单，避免单个不活跃的参与者阻塞整个抽签。
+
+use crate::math::mix_u128;
+use crate::rng::DdRng;
+use crate::selection::{collect_eligible, draw_distinct, Eligibility};
+use crate::{Error, Result};
+
+/// 缺席者的处理策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbsenteePolicy {
+    /// 缺席者不计入随机性，但仍可被选中
+    Exclude,
+    /// 用上一轮信标输出为每个缺席者派生替代值：`mix(beacon, 索引)`，各缺席者的替代值互不相同，
+    /// 不会在异或中相互抵消；缺席者仍可被选中
+    SubstitutePrevious {
+        /// 上一轮信标输出
+        beacon: u128,
+    },
+    /// 缺席者不计入随机性，且在`select_k_threshold`中不能被选中
+    Penalize,
+}
+
+/// 门限随机数的结果报告
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThresholdReport {
+    /// 组合后的随机数
+    pub value: u128,
+    /// 提交了值的参与者数
+    pub present: usize,
+    /// 缺席的参与者数（缺席者索引按升序写入调用者提供的`missing`切片的前`missing`项）
+    pub missing: usize,
+}
+
+/// 在至少t位参与者提交时生成去中心化随机数。
+///
+/// 提交的值按异或组合；缺席者按`policy`处理。n仍须是2的n次幂，以便与
+/// `get_one_dd_rand_num`保持一致（全员提交时结果与其相同）。
+///
+/// # 参数
+///
+/// * `contributions` - 每位参与者的提交值，`None`表示缺席（长度n必须是2的n次幂）
+/// * `t` - 门限：至少需要的提交人数（1 <= t <= n）
+/// * `policy` - 缺席者的处理策略
+/// * `missing` - 用于存储缺席者索引的可变切片（长度必须 >= n - t）
+///
+/// # 返回值
+///
+/// * `Result<ThresholdReport>` - 成功时返回结果报告；提交人数少于t时返回NotEnoughParticipants
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{get_one_dd_rand_num_threshold, AbsenteePolicy};
+///
+/// let contributions = [Some(100u128), None, Some(300), Some(400)];
+/// let mut missing = [0usize; 1];
+/// let report =
+///     get_one_dd_rand_num_threshold(&contributions, 3, AbsenteePolicy::Exclude, &mut missing)
+///         .unwrap();
+/// assert_eq!(report.value, 100 ^ 300 ^ 400);
+/// assert_eq!(report.missing, 1);
+/// assert_eq!(missing, [1]);
+/// ```
+pub fn get_one_dd_rand_num_threshold(
+    contributions: &[Option<u128>],
+    t: usize,
+    policy: AbsenteePolicy,
+    missing: &mut [usize],
+) -> Result<ThresholdReport> {
+    // 验证输入参数
+    let n = contributions.len();
+    if n == 0 || n & (n - 1) != 0 || t == 0 || t > n || missing.len() < n - t {
+        return Err(Error::InvalidInput);
+    }
+    let present = contributions.iter().filter(|c| c.is_some()).count();
+    if present < t {
+        return Err(Error::NotEnoughParticipants);
+    }
+
+    let mut value = 0u128;
+    let mut absent = 0usize;
+    for (i, c) in contributions.iter().enumerate() {
+        match *c {
+            Some(v) => value ^= v,
+            None => {
+                missing[absent] = i;
+                absent += 1;
+                if let AbsenteePolicy::SubstitutePrevious { beacon } = policy {
+                    value ^= mix_u128(beacon, i as u128);
+                }
+            }
+        }
+    }
+
+    Ok(ThresholdReport {
+        value,
+        present,
+        missing: absent,
+    })
+}
+
+/// 在至少t位参与者提交时，从n位参与者中不重复地选出k个。
+///
+/// 随机数由`get_one_dd_rand_num_threshold`生成，再以`DdRng`在资格集合上执行
+/// 部分Fisher–Yates洗牌。`Penalize`策略下缺席者不能被选中，其他策略下所有参与者都有资格。
+///
+/// # 参数
+///
+/// * `contributions` - 每位参与者的提交值，`None`表示缺席（长度n必须是2的n次幂）
+/// * `t` - 门限：至少需要的提交人数（1 <= t <= n）
+/// * `policy` - 缺席者的处理策略
+/// * `scratch` - 临时缓冲区（长度必须 >= n）
+/// * `missing` - 用于存储缺席者索引的可变切片（长度必须 >= n - t）
+/// * `output` - 用于存储k个选中参与者索引的可变切片
+///
+/// # 返回值
+///
+/// * `Result<ThresholdReport>` - 成功时返回结果报告；提交人数少于t或有资格者少于k时
+///   返回NotEnoughParticipants
+pub fn select_k_threshold(
+    contributions: &[Option<u128>],
+    t: usize,
+    policy: AbsenteePolicy,
+    scratch: &mut [usize],
+    missing: &mut [usize],
+    output: &mut [usize],
+) -> Result<ThresholdReport> {
+    if output.is_empty() {
+        return Err(Error::InvalidInput);
+    }
+    let report = get_one_dd_rand_num_threshold(contributions, t, policy, missing)?;
+    let eligibility = match policy {
+        AbsenteePolicy::Penalize => Eligibility::Except(&missing[..report.missing]),
+        _ => Eligibility::All,
+    };
+    let m = collect_eligible(contributions.len(), eligibility, scratch)?;
+    let mut rng = DdRng::new(report.value);
+    draw_distinct(&mut rng, &mut scratch[..m], output)?;
+    Ok(report)
+}
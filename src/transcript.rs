@@ -0,0 +1,364 @@
+//! 可验证的抽签记录。
+//!
+//! 第三方原本只能拿到完全相同的输入重新调用函数来验证一次抽签。本模块定义
+//! `DrawTranscript`，完整记录一次抽签的输入（参与者ID、贡献值及可选的承诺）、
+//! 参数（n、k、排除列表、组合器、算法版本）和输出，并提供：
+//!
+//! * 规范的二进制编码（`encode`），相同的记录总是得到相同的字节串
+//! * 编码的SHA-256摘要（`digest`），可发布到链上或公告中作为锚点
+//! * `verify_transcript`：检查承诺、重新计算抽签并与记录的输出比较
+//!
+//! # 编码格式（整数均为小端序）
+//!
+//! ```text
+//! "DDTR" | version: u16 | 算法标签: u8 | [信标: 组合器: u8 | 上一轮输出: u128]
+//! | n: u64 | k: u64
+//! | 参与者数: u64 | 参与者ID: u64 ...
+//! | 贡献值数: u64 | 贡献值: u128 ...
+//! | 承诺数: u64 | 承诺: [u8; 32] ...
+//! | 排除数: u64 | 排除索引: u64 ...
+//! | 输出数: u64 | 输出: u128 ...
+//! ```
+
+use crate::algorithms::{
+    get_k_dd_rand_num, get_k_dd_rand_num_with_whitelist, get_one_dd_3d_rand_num,
+    get_one_dd_rand_num,
+};
+use crate::beacon::{combine_beacon, BeaconCombiner};
+use crate::sha256::{sha256, Sha256};
+use crate::types::ParticipantId;
+use crate::{Error, Result};
+
+/// 当前的记录格式与算法版本
+pub const DRAW_TRANSCRIPT_VERSION: u16 = 1;
+
+/// 编码的魔数
+pub const DRAW_TRANSCRIPT_MAGIC: [u8; 4] = *b"DDTR";
+
+/// `verify_transcript`支持的最大参与者数
+pub const MAX_TRANSCRIPT_PARTICIPANTS: usize = 256;
+
+/// 贡献值承诺的域分隔前缀
+const COMMITMENT_TAG: [u8; 4] = *b"DDCM";
+
+/// 记录所对应的抽签算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawAlgorithm {
+    /// `get_one_dd_rand_num`：每位参与者1个贡献值，输出1个随机数
+    OneRandNum,
+    /// `get_one_dd_3d_rand_num`：每位参与者1个贡献值，k为号码范围，输出1个号码
+    ThreeDRandNum,
+    /// `get_k_dd_rand_num`（排除列表非空时为`get_k_dd_rand_num_with_whitelist`）：
+    /// 每位参与者k个贡献值（按行排列），输出k个选中索引
+    KRandNum,
+    /// `next_beacon_round`的一轮：每位参与者1个贡献值，输出本轮信标值
+    BeaconRound {
+        /// 组合方式
+        combiner: BeaconCombiner,
+        /// 上一轮信标输出
+        previous_output: u128,
+    },
+}
+
+impl DrawAlgorithm {
+    fn tag(&self) -> u8 {
+        match self {
+            DrawAlgorithm::OneRandNum => 1,
+            DrawAlgorithm::ThreeDRandNum => 2,
+            DrawAlgorithm::KRandNum => 3,
+            DrawAlgorithm::BeaconRound { .. } => 4,
+        }
+    }
+
+    /// 每位参与者的贡献值数量
+    fn values_per_participant(&self, k: usize) -> usize {
+        match self {
+            DrawAlgorithm::KRandNum => k,
+            _ => 1,
+        }
+    }
+}
+
+/// 一次抽签的完整记录
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrawTranscript<'a> {
+    /// 记录格式与算法版本（当前为`DRAW_TRANSCRIPT_VERSION`）
+    pub version: u16,
+    /// 抽签算法
+    pub algorithm: DrawAlgorithm,
+    /// 参与者ID（第i项对应贡献值中的第i位参与者）
+    pub participants: &'a [ParticipantId],
+    /// 贡献值：`KRandNum`为n行k列按行排列，其他算法为n个值
+    pub contributions: &'a [u128],
+    /// 承诺阶段公布的承诺（为空或恰好n个，见`contribution_commitment`）
+    pub commitments: &'a [[u8; 32]],
+    /// 参与者数量
+    pub n: usize,
+    /// `KRandNum`为选择数量，`ThreeDRandNum`为号码范围，其他算法为0
+    pub k: usize,
+    /// 不能被选中的索引（严格升序，仅`KRandNum`可非空）
+    pub exclusions: &'a [usize],
+    /// 抽签输出：随机数、号码、信标值或选中索引
+    pub outputs: &'a [u128],
+}
+
+impl DrawTranscript<'_> {
+    /// 按规范编码依次输出各字段
+    fn write_fields(&self, sink: &mut dyn FnMut(&[u8])) {
+        sink(&DRAW_TRANSCRIPT_MAGIC);
+        sink(&self.version.to_le_bytes());
+        sink(&[self.algorithm.tag()]);
+        if let DrawAlgorithm::BeaconRound {
+            combiner,
+            previous_output,
+        } = self.algorithm
+        {
+            let combiner_tag = match combiner {
+                BeaconCombiner::Xor => 0u8,
+                BeaconCombiner::ChaCha20 => 1u8,
+            };
+            sink(&[combiner_tag]);
+            sink(&previous_output.to_le_bytes());
+        }
+        sink(&(self.n as u64).to_le_bytes());
+        sink(&(self.k as u64).to_le_bytes());
+
+        sink(&(self.participants.len() as u64).to_le_bytes());
+        for id in self.participants {
+            sink(&id.to_le_bytes());
+        }
+        sink(&(self.contributions.len() as u64).to_le_bytes());
+        for v in self.contributions {
+            sink(&v.to_le_bytes());
+        }
+        sink(&(self.commitments.len() as u64).to_le_bytes());
+        for c in self.commitments {
+            sink(c);
+        }
+        sink(&(self.exclusions.len() as u64).to_le_bytes());
+        for &e in self.exclusions {
+            sink(&(e as u64).to_le_bytes());
+        }
+        sink(&(self.outputs.len() as u64).to_le_bytes());
+        for v in self.outputs {
+            sink(&v.to_le_bytes());
+        }
+    }
+
+    /// 规范编码的字节长度
+    pub fn encoded_len(&self) -> usize {
+        let mut len = 0usize;
+        self.write_fields(&mut |bytes| len += bytes.len());
+        len
+    }
+
+    /// 将记录写入规范的二进制编码。
+    ///
+    /// # 参数
+    ///
+    /// * `buf` - 输出缓冲区（长度必须 >= `encoded_len()`）
+    ///
+    /// # 返回值
+    ///
+    /// * `Result<usize>` - 成功时返回写入的字节数，缓冲区不足时返回InvalidInput
+    pub fn encode(&self, buf: &mut [u8]) -> Result<usize> {
+        let len = self.encoded_len();
+        if buf.len() < len {
+            return Err(Error::InvalidInput);
+        }
+        let mut pos = 0usize;
+        self.write_fields(&mut |bytes| {
+            buf[pos..pos + bytes.len()].copy_from_slice(bytes);
+            pos += bytes.len();
+        });
+        Ok(pos)
+    }
+
+    /// 规范编码的SHA-256摘要
+    pub fn digest(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        self.write_fields(&mut |bytes| hasher.update(bytes));
+        hasher.finalize()
+    }
+}
+
+/// 计算一位参与者贡献值的承诺：SHA-256("DDCM" ‖ 参与者ID ‖ 值的数量 ‖ 各值)（小端序）。
+///
+/// 参与者在承诺阶段公布承诺，揭示阶段公布贡献值，`verify_transcript`会检查二者一致。
+///
+/// # 参数
+///
+/// * `participant` - 参与者ID
+/// * `values` - 该参与者的贡献值（`KRandNum`为该参与者的一行k个值）
+///
+/// # 返回值
+///
+/// * `[u8; 32]` - 承诺
+pub fn contribution_commitment(participant: ParticipantId, values: &[u128]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(&COMMITMENT_TAG);
+    hasher.update(&participant.to_le_bytes());
+    hasher.update(&(values.len() as u64).to_le_bytes());
+    for v in values {
+        hasher.update(&v.to_le_bytes());
+    }
+    hasher.finalize()
+}
+
+/// 独立验证一份抽签记录。
+///
+/// 检查记录结构，逐一核对承诺（若有），再按记录的算法和参数重新计算抽签，
+/// 与记录的输出比较。
+///
+/// # 参数
+///
+/// * `transcript` - 抽签记录（参与者数 <= 256）
+///
+/// # 返回值
+///
+/// * `Result<[u8; 32]>` - 验证通过时返回记录的摘要；记录结构无效或版本不支持时返回InvalidInput，
+///   承诺或输出不一致时返回CalculationFailed
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{
+///     contribution_commitment, get_k_dd_rand_num, verify_transcript, DrawAlgorithm,
+///     DrawTranscript, DRAW_TRANSCRIPT_VERSION,
+/// };
+///
+/// let participants = [11u64, 22, 33, 44];
+/// let contributions = [1u128, 2, 30, 40, 500, 600, 7_000, 8_000];
+/// let commitments: Vec<[u8; 32]> = participants
+///     .iter()
+///     .zip(contributions.chunks(2))
+///     .map(|(&id, row)| contribution_commitment(id, row))
+///     .collect();
+///
+/// let groups: Vec<&[u128]> = contributions.chunks(2).collect();
+/// let mut selected = [0usize; 2];
+/// get_k_dd_rand_num(&groups, 4, 2, &mut selected).unwrap();
+/// let outputs = selected.map(|i| i as u128);
+///
+/// let transcript = DrawTranscript {
+///     version: DRAW_TRANSCRIPT_VERSION,
+///     algorithm: DrawAlgorithm::KRandNum,
+///     participants: &participants,
+///     contributions: &contributions,
+///     commitments: &commitments,
+///     n: 4,
+///     k: 2,
+///     exclusions: &[],
+///     outputs: &outputs,
+/// };
+/// assert_eq!(verify_transcript(&transcript).unwrap(), transcript.digest());
+///
+/// // 篡改输出后验证失败
+/// let forged = [outputs[1], outputs[0]];
+/// assert!(verify_transcript(&DrawTranscript { outputs: &forged, ..transcript }).is_err());
+/// ```
+pub fn verify_transcript(transcript: &DrawTranscript) -> Result<[u8; 32]> {
+    let t = transcript;
+    let n = t.n;
+    let k = t.k;
+    if t.version != DRAW_TRANSCRIPT_VERSION || n == 0 || n > MAX_TRANSCRIPT_PARTICIPANTS {
+        return Err(Error::InvalidInput);
+    }
+    let per = t.algorithm.values_per_participant(k);
+    // k个选择不能超过参与者数，否则后续按k切片会越界
+    if t.algorithm == DrawAlgorithm::KRandNum && k > n {
+        return Err(Error::InvalidInput);
+    }
+    if per == 0
+        || t.participants.len() != n
+        || n.checked_mul(per) != Some(t.contributions.len())
+        || (!t.commitments.is_empty() && t.commitments.len() != n)
+    {
+        return Err(Error::InvalidInput);
+    }
+    // 排除列表必须严格升序（保证编码规范），且只有KRandNum可以使用
+    if t.exclusions.windows(2).any(|w| w[0] >= w[1])
+        || t.exclusions.last().is_some_and(|&e| e >= n)
+        || (!t.exclusions.is_empty() && t.algorithm != DrawAlgorithm::KRandNum)
+    {
+        return Err(Error::InvalidInput);
+    }
+
+    // 承诺
+    for ((commitment, &id), row) in t
+        .commitments
+        .iter()
+        .zip(t.participants)
+        .zip(t.contributions.chunks(per))
+    {
+        if contribution_commitment(id, row) != *commitment {
+            return Err(Error::CalculationFailed);
+        }
+    }
+
+    // 重新计算
+    let mut expected = [0u128; MAX_TRANSCRIPT_PARTICIPANTS];
+    let produced = match t.algorithm {
+        DrawAlgorithm::OneRandNum | DrawAlgorithm::BeaconRound { .. } => {
+            if k != 0 {
+                return Err(Error::InvalidInput);
+            }
+            get_one_dd_rand_num(t.contributions, n, &mut expected[0])?;
+            if let DrawAlgorithm::BeaconRound {
+                combiner,
+                previous_output,
+            } = t.algorithm
+            {
+                expected[0] = combine_beacon(previous_output, expected[0], combiner);
+            }
+            1
+        }
+        DrawAlgorithm::ThreeDRandNum => {
+            get_one_dd_3d_rand_num(t.contributions, n, k, &mut expected[0])?;
+            1
+        }
+        DrawAlgorithm::KRandNum => {
+            let mut groups: [&[u128]; MAX_TRANSCRIPT_PARTICIPANTS] =
+                [&[]; MAX_TRANSCRIPT_PARTICIPANTS];
+            for (g, row) in groups.iter_mut().zip(t.contributions.chunks(k)) {
+                *g = row;
+            }
+            let mut selected = [0usize; MAX_TRANSCRIPT_PARTICIPANTS];
+            if t.exclusions.is_empty() {
+                get_k_dd_rand_num(&groups[..n], n, k, &mut selected[..k])?;
+            } else {
+                get_k_dd_rand_num_with_whitelist(
+                    &groups[..n],
+                    n,
+                    k,
+                    t.exclusions,
+                    &mut selected[..k],
+                )?;
+            }
+            for (e, &s) in expected.iter_mut().zip(&selected[..k]) {
+                *e = s as u128;
+            }
+            k
+        }
+    };
+
+    if t.outputs != &expected[..produced] {
+        return Err(Error::CalculationFailed);
+    }
+    Ok(t.digest())
+}
+
+/// 对编码后的记录计算摘要，与`DrawTranscript::digest`一致。
+///
+/// 适用于只拿到已发布字节串的验证者。
+///
+/// # 参数
+///
+/// * `encoded` - `DrawTranscript::encode`产生的字节串
+///
+/// # 返回值
+///
+/// * `[u8; 32]` - SHA-256摘要
+pub fn transcript_digest(encoded: &[u8]) -> [u8; 32] {
+    sha256(encoded)
+}
@@ -0,0 +1,216 @@
+//! ECVRF-EDWARDS25519-SHA512-TAI（RFC 9381）验证与随机数适配器。
+//!
+//! 部分参与者希望提交VRF输出而不是任意的u128值：VRF输出由私钥和公共输入（如轮次号）
+//! 唯一确定，参与者无法在看到他人的值之后改变自己的贡献。本模块验证RFC 9381中
+//! 套件0x03（ECVRF-EDWARDS25519-SHA512-TAI）的证明，并将验证通过的VRF输出转换为
+//! `get_one_dd_rand_num`使用的`values`或`get_k_dd_rand_num`使用的`groups`。
+//!
+//! 需要启用`vrf`特性。
+
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use sha2::{Digest, Sha512};
+
+use crate::rng::DdRng;
+use crate::{Error, Result};
+
+/// 套件标识：ECVRF-EDWARDS25519-SHA512-TAI
+const SUITE_STRING: u8 = 0x03;
+
+/// 证明长度：Gamma（32字节）‖ c（16字节）‖ s（32字节）
+pub const VRF_PROOF_LEN: usize = 80;
+
+/// VRF输出（beta）长度
+pub const VRF_OUTPUT_LEN: usize = 64;
+
+/// 一位参与者的VRF贡献
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VrfContribution<'a> {
+    /// Ed25519公钥（32字节压缩点）
+    pub public_key: &'a [u8; 32],
+    /// 对公共输入`alpha`的VRF证明
+    pub proof: &'a [u8; VRF_PROOF_LEN],
+}
+
+/// 按RFC 8032解码点，拒绝非规范编码
+fn string_to_point(bytes: &[u8]) -> Option<EdwardsPoint> {
+    let mut buf = [0u8; 32];
+    buf.copy_from_slice(bytes);
+    let point = CompressedEdwardsY(buf).decompress()?;
+    if point.compress().to_bytes() != buf {
+        return None;
+    }
+    Some(point)
+}
+
+/// ECVRF_encode_to_curve_try_and_increment（RFC 9381 第5.4.1.1节）
+fn encode_to_curve(salt: &[u8; 32], alpha: &[u8]) -> Option<EdwardsPoint> {
+    for ctr in 0..=255u8 {
+        let hash = Sha512::new()
+            .chain_update([SUITE_STRING, 0x01])
+            .chain_update(salt)
+            .chain_update(alpha)
+            .chain_update([ctr, 0x00])
+            .finalize();
+        if let Some(point) = string_to_point(&hash[..32]) {
+            let h = point.mul_by_cofactor();
+            if h != EdwardsPoint::default() {
+                return Some(h);
+            }
+        }
+    }
+    None
+}
+
+/// ECVRF_challenge_generation（RFC 9381 第5.4.3节），返回截断后的16字节c
+fn challenge(points: [&EdwardsPoint; 5]) -> [u8; 16] {
+    let mut hasher = Sha512::new();
+    hasher.update([SUITE_STRING, 0x02]);
+    for p in points {
+        hasher.update(p.compress().as_bytes());
+    }
+    hasher.update([0x00]);
+    let hash = hasher.finalize();
+    let mut c = [0u8; 16];
+    c.copy_from_slice(&hash[..16]);
+    c
+}
+
+/// 验证ECVRF-EDWARDS25519-SHA512-TAI证明并返回VRF输出。
+///
+/// 按RFC 9381第5.3节实现，包括公钥验证（拒绝小阶点）。
+///
+/// # 参数
+///
+/// * `public_key` - Ed25519公钥（32字节压缩点）
+/// * `alpha` - 公共输入（如轮次号的字节表示）
+/// * `proof` - 80字节证明
+///
+/// # 返回值
+///
+/// * `Result<[u8; 64]>` - 验证通过时返回VRF输出beta；公钥或证明编码无效时返回InvalidInput，
+///   证明与公钥和输入不匹配时返回CalculationFailed
+pub fn ecvrf_verify(
+    public_key: &[u8; 32],
+    alpha: &[u8],
+    proof: &[u8; VRF_PROOF_LEN],
+) -> Result<[u8; VRF_OUTPUT_LEN]> {
+    // ECVRF_validate_key
+    let y = string_to_point(public_key).ok_or(Error::InvalidInput)?;
+    if y.is_small_order() {
+        return Err(Error::InvalidInput);
+    }
+
+    // ECVRF_decode_proof
+    let gamma = string_to_point(&proof[..32]).ok_or(Error::InvalidInput)?;
+    let mut c_bytes = [0u8; 32];
+    c_bytes[..16].copy_from_slice(&proof[32..48]);
+    let c = Scalar::from_bytes_mod_order(c_bytes);
+    let mut s_bytes = [0u8; 32];
+    s_bytes.copy_from_slice(&proof[48..]);
+    let s =
+        Option::<Scalar>::from(Scalar::from_canonical_bytes(s_bytes)).ok_or(Error::InvalidInput)?;
+
+    let h = encode_to_curve(public_key, alpha).ok_or(Error::CalculationFailed)?;
+
+    // U = s*B - c*Y，V = s*H - c*Gamma
+    let u = EdwardsPoint::vartime_double_scalar_mul_basepoint(&(-c), &y, &s);
+    let v = h * s - gamma * c;
+
+    if challenge([&y, &h, &gamma, &u, &v])[..] != proof[32..48] {
+        return Err(Error::CalculationFailed);
+    }
+
+    // ECVRF_proof_to_hash
+    let hash = Sha512::new()
+        .chain_update([SUITE_STRING, 0x03])
+        .chain_update(gamma.mul_by_cofactor().compress().as_bytes())
+        .chain_update([0x00])
+        .finalize();
+    let mut beta = [0u8; VRF_OUTPUT_LEN];
+    beta.copy_from_slice(&hash);
+    Ok(beta)
+}
+
+/// 验证每位参与者的VRF证明，并转换为`get_one_dd_rand_num`的`values`。
+///
+/// 每个值取VRF输出的前16字节（小端序）。任何一个证明无效都会返回错误。
+///
+/// # 参数
+///
+/// * `contributions` - 每位参与者的VRF贡献
+/// * `alpha` - 所有参与者共同使用的公共输入
+/// * `values` - 用于存储转换结果的可变切片（应与contributions.len()匹配）
+///
+/// # 返回值
+///
+/// * `Result<()>` - 成功时返回Ok，证明无效时返回`ecvrf_verify`的错误
+///
+/// # 示例
+///
+/// ```
+/// use dd_algorithms_lib::{vrf_contributions_to_values, VrfContribution};
+///
+/// let pk: [u8; 32] = [
+///     0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9, 0x64,
+///     0x07, 0x3a, 0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02, 0x1a, 0x68,
+///     0xf7, 0x07, 0x51, 0x1a,
+/// ];
+/// let forged = [0u8; 80];
+/// let contributions = [VrfContribution { public_key: &pk, proof: &forged }];
+/// let mut values = [0u128; 1];
+/// assert!(vrf_contributions_to_values(&contributions, b"", &mut values).is_err());
+/// ```
+pub fn vrf_contributions_to_values(
+    contributions: &[VrfContribution],
+    alpha: &[u8],
+    values: &mut [u128],
+) -> Result<()> {
+    if contributions.is_empty() || values.len() != contributions.len() {
+        return Err(Error::InvalidInput);
+    }
+    for (c, value) in contributions.iter().zip(values.iter_mut()) {
+        let beta = ecvrf_verify(c.public_key, alpha, c.proof)?;
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(&beta[..16]);
+        *value = u128::from_le_bytes(bytes);
+    }
+    Ok(())
+}
+
+/// 验证每位参与者的VRF证明，并展开为`get_k_dd_rand_num`的`groups`。
+///
+/// 每位参与者的k个值由`DdRng::from_key(beta[..32])`的随机数流依次取`next_u128`得到，
+/// 按行写入`buffer`：第i位参与者的值位于`buffer[i * k..(i + 1) * k]`，
+/// 调用者可用`buffer.chunks(k)`构造`groups`。
+///
+/// # 参数
+///
+/// * `contributions` - 每位参与者的VRF贡献
+/// * `alpha` - 所有参与者共同使用的公共输入
+/// * `k` - 每位参与者需要的值的数量
+/// * `buffer` - 用于存储展开结果的可变切片（长度应为 contributions.len() * k）
+///
+/// # 返回值
+///
+/// * `Result<()>` - 成功时返回Ok，证明无效时返回`ecvrf_verify`的错误
+pub fn vrf_contributions_to_groups(
+    contributions: &[VrfContribution],
+    alpha: &[u8],
+    k: usize,
+    buffer: &mut [u128],
+) -> Result<()> {
+    if contributions.is_empty() || k == 0 || buffer.len() != contributions.len() * k {
+        return Err(Error::InvalidInput);
+    }
+    for (c, row) in contributions.iter().zip(buffer.chunks_mut(k)) {
+        let beta = ecvrf_verify(c.public_key, alpha, c.proof)?;
+        let mut key = [0u8; 32];
+        key.copy_from_slice(&beta[..32]);
+        let mut rng = DdRng::from_key(key);
+        for v in row.iter_mut() {
+            *v = rng.next_u128();
+        }
+    }
+    Ok(())
+}